@@ -1,7 +1,10 @@
 use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 
 use bytes::Bytes;
-use futures::TryStream;
+use futures::{Stream, TryStream, TryStreamExt};
 use http::HeaderValue;
 use http::header::{CONTENT_LENGTH, CONTENT_TYPE};
 use reqwest::Body;
@@ -9,6 +12,7 @@ use serde::Serialize;
 
 use crate::BoxError;
 use crate::error::Result;
+use crate::utils::Crc64;
 
 pub trait MakeBody {
     type Body;
@@ -38,6 +42,29 @@ impl MakeBody for ZeroBody {
     }
 }
 
+/// A request body backed by a single in-memory [`Bytes`] buffer, attached via
+/// [`reqwest::Body::from`] rather than [`StreamBody`]'s [`reqwest::Body::wrap_stream`].
+///
+/// This is the difference that lets [`Client::request`](crate::Client)'s retry loop
+/// safely re-send the request: `reqwest::Request::try_clone` only succeeds for a body
+/// built from an in-memory buffer, never for a wrapped stream (even one that happens to
+/// yield a single chunk), since a stream generally can't be replayed. Operations whose
+/// body is a buffered, already-known-safe-to-resend value (e.g. `UploadPart`'s
+/// `upload_part`, as opposed to the non-replayable `upload_part_stream`) should use this
+/// instead of `StreamBody` to make retries possible.
+pub struct BytesBody;
+
+impl MakeBody for BytesBody {
+    type Body = Bytes;
+
+    fn make_body(body: Self::Body, request: &mut reqwest::Request) -> Result<()> {
+        let headers = request.headers_mut();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_str(&body.len().to_string())?);
+        request.body_mut().replace(Body::from(body));
+        Ok(())
+    }
+}
+
 pub struct XMLBody<T>(PhantomData<T>);
 
 impl<T> MakeBody for XMLBody<T>
@@ -71,3 +98,49 @@ where
         Ok(())
     }
 }
+
+/// A type-erased `Bytes` stream, used to compose `StreamBody` adapters (client-side
+/// encryption, CRC64 verification, ...) without monomorphizing every combination of them.
+pub(crate) type BoxedByteStream = Pin<Box<dyn Stream<Item = std::result::Result<Bytes, BoxError>> + Send>>;
+
+/// Erase `stream`'s concrete type into a [`BoxedByteStream`].
+pub(crate) fn box_byte_stream<S>(stream: S) -> BoxedByteStream
+where
+    S: TryStream + Send + 'static,
+    S::Error: Into<BoxError>,
+    Bytes: From<S::Ok>,
+{
+    Box::pin(stream.map_ok(Bytes::from).map_err(Into::into))
+}
+
+/// A pass-through [`Stream`] adapter that folds every chunk into a shared [`Crc64`]
+/// accumulator as it streams by, so the running checksum of a body can be compared
+/// against the server's reported CRC without buffering it. Used for both request bodies
+/// (uploads) and, once wrapped around a response body, streamed downloads.
+pub(crate) struct Crc64Stream {
+    inner: BoxedByteStream,
+    hasher: Arc<Mutex<Crc64>>,
+}
+
+impl Crc64Stream {
+    pub(crate) fn new(inner: BoxedByteStream, hasher: Arc<Mutex<Crc64>>) -> Self {
+        Self { inner, hasher }
+    }
+}
+
+impl Stream for Crc64Stream {
+    type Item = std::result::Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this.inner.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                if let Ok(mut hasher) = this.hasher.lock() {
+                    hasher.update(&chunk);
+                }
+                Poll::Ready(Some(Ok(chunk)))
+            },
+            other => other,
+        }
+    }
+}
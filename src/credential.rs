@@ -1,18 +1,27 @@
 use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt::Write;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use chrono::Utc;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use chrono::{DateTime, Utc};
 use http::header::{AUTHORIZATION, DATE};
 use http::{HeaderMap, HeaderValue};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
 
+use crate::error::Error;
+use crate::ser::percent_encode;
 use crate::utils::escape_path;
-use crate::{QueryAuthOptions, ser};
+use crate::{ser, QueryAuthOptions};
 
 const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
-const SIGNATURE_VERSION: &str = "OSS4-HMAC-SHA256";
+pub(crate) const SIGNATURE_VERSION: &str = "OSS4-HMAC-SHA256";
 
 #[derive(Clone, Debug)]
 pub(crate) struct Credential {
@@ -21,6 +30,472 @@ pub(crate) struct Credential {
     pub security_token: Option<String>,
 }
 
+/// A credential handed back by a [`CredentialProvider`]: the access key pair, the
+/// optional STS `security_token` that comes with temporary (RAM-role) credentials, and
+/// when it stops being valid.
+#[derive(Clone, Debug)]
+pub struct ProvidedCredential {
+    pub access_key_id: String,
+    pub access_key_secret: String,
+    pub security_token: Option<String>,
+    /// When this credential expires, if it's temporary. `None` means it never expires
+    /// (e.g. a static long-lived key pair).
+    pub expires_at: Option<Instant>,
+}
+
+impl ProvidedCredential {
+    /// A credential that never expires, e.g. a long-lived access key pair.
+    pub fn non_expiring(
+        access_key_id: impl Into<String>,
+        access_key_secret: impl Into<String>,
+    ) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            access_key_secret: access_key_secret.into(),
+            security_token: None,
+            expires_at: None,
+        }
+    }
+
+    fn is_valid(&self, skew: Duration) -> bool {
+        match self.expires_at {
+            Some(expires_at) => Instant::now() + skew < expires_at,
+            None => true,
+        }
+    }
+}
+
+impl From<ProvidedCredential> for Credential {
+    fn from(value: ProvidedCredential) -> Self {
+        Credential {
+            access_key_id: value.access_key_id,
+            access_key_secret: value.access_key_secret,
+            security_token: value.security_token,
+        }
+    }
+}
+
+/// Supplies the [`ProvidedCredential`] used to sign each request.
+///
+/// Implement this to source credentials from somewhere other than a fixed key pair, e.g.
+/// the ECS/ECI metadata endpoint or STS `AssumeRole`, so temporary credentials are
+/// refreshed instead of silently going stale mid-process. [`StaticCredentialProvider`] and
+/// [`RefreshingCredentialProvider`] cover the common cases.
+pub trait CredentialProvider: Send + Sync + std::fmt::Debug {
+    /// Return the credential to sign the next request with, refreshing it first if needed.
+    fn credential(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<ProvidedCredential>> + Send + '_>>;
+}
+
+/// A [`CredentialProvider`] that always returns the same credential, captured once at
+/// construction time. This is the default, matching the behavior before credential
+/// providers were pluggable.
+#[derive(Debug)]
+pub struct StaticCredentialProvider {
+    credential: ProvidedCredential,
+}
+
+impl StaticCredentialProvider {
+    pub fn new(credential: ProvidedCredential) -> Self {
+        Self { credential }
+    }
+}
+
+impl CredentialProvider for StaticCredentialProvider {
+    fn credential(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<ProvidedCredential>> + Send + '_>> {
+        Box::pin(async move { Ok(self.credential.clone()) })
+    }
+}
+
+type RefreshFn = Arc<
+    dyn Fn() -> Pin<Box<dyn Future<Output = crate::Result<ProvidedCredential>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// A [`CredentialProvider`] that caches the credential behind an [`RwLock`], treats it as
+/// valid until `expires_at - skew`, and refreshes it through a user-supplied async closure
+/// (e.g. one that queries the ECS metadata endpoint or calls STS `AssumeRole`) once it
+/// goes stale. Concurrent callers that observe a stale credential block on a single
+/// refresh instead of each kicking off their own.
+pub struct RefreshingCredentialProvider {
+    skew: Duration,
+    refresh: RefreshFn,
+    cached: RwLock<Option<ProvidedCredential>>,
+    refresh_lock: Mutex<()>,
+}
+
+impl std::fmt::Debug for RefreshingCredentialProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshingCredentialProvider")
+            .finish_non_exhaustive()
+    }
+}
+
+impl RefreshingCredentialProvider {
+    /// `skew` is subtracted from `expires_at` so a refresh kicks off slightly before the
+    /// credential actually expires, leaving headroom for the refresh itself and clock drift.
+    pub fn new<F, Fut>(skew: Duration, refresh: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::Result<ProvidedCredential>> + Send + 'static,
+    {
+        Self {
+            skew,
+            refresh: Arc::new(move || {
+                Box::pin(refresh()) as Pin<Box<dyn Future<Output = _> + Send>>
+            }),
+            cached: RwLock::new(None),
+            refresh_lock: Mutex::new(()),
+        }
+    }
+
+    async fn valid_cached(&self) -> Option<ProvidedCredential> {
+        let cached = self.cached.read().await;
+        cached.as_ref().filter(|c| c.is_valid(self.skew)).cloned()
+    }
+}
+
+impl CredentialProvider for RefreshingCredentialProvider {
+    fn credential(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<ProvidedCredential>> + Send + '_>> {
+        Box::pin(async move {
+            if let Some(credential) = self.valid_cached().await {
+                return Ok(credential);
+            }
+
+            // Single-flight: whoever gets here first refreshes; everyone else waits on
+            // the lock, then re-checks the now-fresh cache instead of refreshing again.
+            let _guard = self.refresh_lock.lock().await;
+            if let Some(credential) = self.valid_cached().await {
+                return Ok(credential);
+            }
+
+            let fresh = (self.refresh)().await?;
+            *self.cached.write().await = Some(fresh.clone());
+            Ok(fresh)
+        })
+    }
+}
+
+/// Env vars consulted by [`EnvCredentialProvider`] and [`DefaultCredentialProviderChain`].
+const ENV_ACCESS_KEY_ID: &str = "OSS_ACCESS_KEY_ID";
+const ENV_ACCESS_KEY_SECRET: &str = "OSS_ACCESS_KEY_SECRET";
+const ENV_SESSION_TOKEN: &str = "OSS_SESSION_TOKEN";
+const ENV_ROLE_ARN: &str = "OSS_ROLE_ARN";
+const ENV_ROLE_SESSION_NAME: &str = "OSS_ROLE_SESSION_NAME";
+const ENV_ECS_RAM_ROLE_NAME: &str = "OSS_ECS_RAM_ROLE_NAME";
+
+/// Base URL for the ECS/ACK instance metadata RAM-role endpoint. Appending nothing returns
+/// the name of the role attached to the instance; appending a role name returns that role's
+/// current temporary credentials as JSON.
+const ECS_METADATA_BASE_URL: &str = "http://100.100.100.200/latest/meta-data/ram/security-credentials/";
+
+/// Default STS endpoint used by [`sts_assume_role_credential_provider`].
+const STS_ENDPOINT: &str = "https://sts.aliyuncs.com/";
+
+/// How long before the upstream-reported `Expiration` a [`RefreshingCredentialProvider`]
+/// built by this module should proactively refresh, leaving headroom for clock drift and the
+/// refresh call itself.
+const DEFAULT_REFRESH_SKEW: Duration = Duration::from_secs(5 * 60);
+
+/// A [`CredentialProvider`] that reads a static key pair from `OSS_ACCESS_KEY_ID` /
+/// `OSS_ACCESS_KEY_SECRET`, with an optional `OSS_SESSION_TOKEN`. The first stage tried by
+/// [`DefaultCredentialProviderChain`].
+#[derive(Debug, Default)]
+pub struct EnvCredentialProvider {
+    _private: (),
+}
+
+impl EnvCredentialProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CredentialProvider for EnvCredentialProvider {
+    fn credential(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<ProvidedCredential>> + Send + '_>> {
+        Box::pin(async move {
+            let access_key_id = std::env::var(ENV_ACCESS_KEY_ID)
+                .map_err(|_| Error::InvalidArgument(format!("{ENV_ACCESS_KEY_ID} is not set")))?;
+            let access_key_secret = std::env::var(ENV_ACCESS_KEY_SECRET).map_err(|_| {
+                Error::InvalidArgument(format!("{ENV_ACCESS_KEY_SECRET} is not set"))
+            })?;
+            let security_token = std::env::var(ENV_SESSION_TOKEN).ok();
+            Ok(ProvidedCredential {
+                access_key_id,
+                access_key_secret,
+                security_token,
+                expires_at: None,
+            })
+        })
+    }
+}
+
+/// Convert a wall-clock expiry reported by OSS/STS into the monotonic [`Instant`]
+/// [`ProvidedCredential::expires_at`] and [`RefreshingCredentialProvider`] work in. An
+/// expiry already in the past maps to "now", so the next call refreshes immediately instead
+/// of panicking on a negative duration.
+fn instant_from_expiration(expiration: DateTime<Utc>) -> Instant {
+    match (expiration - Utc::now()).to_std() {
+        Ok(remaining) => Instant::now() + remaining,
+        Err(_) => Instant::now(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EcsRamRoleCredential {
+    access_key_id: String,
+    access_key_secret: String,
+    security_token: String,
+    expiration: DateTime<Utc>,
+}
+
+/// Build a [`RefreshingCredentialProvider`] that sources temporary credentials from the
+/// ECS/ACK instance metadata service documented at
+/// `http://100.100.100.200/latest/meta-data/ram/security-credentials/`. If `role_name` is
+/// `None`, the role attached to the instance is discovered by GETing the bare endpoint first,
+/// which returns the role name as a bare line of text.
+pub fn ecs_ram_role_credential_provider(
+    http_client: reqwest::Client,
+    role_name: Option<String>,
+) -> RefreshingCredentialProvider {
+    RefreshingCredentialProvider::new(DEFAULT_REFRESH_SKEW, move || {
+        let http_client = http_client.clone();
+        let role_name = role_name.clone();
+        async move {
+            let role_name = match role_name {
+                Some(role_name) => role_name,
+                None => http_client
+                    .get(ECS_METADATA_BASE_URL)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .text()
+                    .await?
+                    .lines()
+                    .next()
+                    .ok_or_else(|| {
+                        Error::Other("ECS metadata service returned no RAM role".to_string())
+                    })?
+                    .to_string(),
+            };
+
+            let credential: EcsRamRoleCredential = http_client
+                .get(format!("{ECS_METADATA_BASE_URL}{role_name}"))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            Ok(ProvidedCredential {
+                access_key_id: credential.access_key_id,
+                access_key_secret: credential.access_key_secret,
+                security_token: Some(credential.security_token),
+                expires_at: Some(instant_from_expiration(credential.expiration)),
+            })
+        }
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct StsAssumeRoleResponse {
+    #[serde(rename = "Credentials")]
+    credentials: StsCredentials,
+}
+
+#[derive(Debug, Deserialize)]
+struct StsCredentials {
+    #[serde(rename = "AccessKeyId")]
+    access_key_id: String,
+    #[serde(rename = "AccessKeySecret")]
+    access_key_secret: String,
+    #[serde(rename = "SecurityToken")]
+    security_token: String,
+    #[serde(rename = "Expiration")]
+    expiration: DateTime<Utc>,
+}
+
+/// Build a [`RefreshingCredentialProvider`] that calls STS `AssumeRole` with the long-lived
+/// `access_key_id`/`access_key_secret` pair to mint temporary credentials scoped to
+/// `role_arn`, refreshing a few minutes before they expire.
+pub fn sts_assume_role_credential_provider(
+    http_client: reqwest::Client,
+    access_key_id: impl Into<String>,
+    access_key_secret: impl Into<String>,
+    role_arn: impl Into<String>,
+    role_session_name: impl Into<String>,
+) -> RefreshingCredentialProvider {
+    let access_key_id = access_key_id.into();
+    let access_key_secret = access_key_secret.into();
+    let role_arn = role_arn.into();
+    let role_session_name = role_session_name.into();
+    RefreshingCredentialProvider::new(DEFAULT_REFRESH_SKEW, move || {
+        let http_client = http_client.clone();
+        let access_key_id = access_key_id.clone();
+        let access_key_secret = access_key_secret.clone();
+        let role_arn = role_arn.clone();
+        let role_session_name = role_session_name.clone();
+        async move {
+            let response = call_sts_assume_role(
+                &http_client,
+                &access_key_id,
+                &access_key_secret,
+                &role_arn,
+                &role_session_name,
+            )
+            .await?;
+
+            Ok(ProvidedCredential {
+                access_key_id: response.credentials.access_key_id,
+                access_key_secret: response.credentials.access_key_secret,
+                security_token: Some(response.credentials.security_token),
+                expires_at: Some(instant_from_expiration(response.credentials.expiration)),
+            })
+        }
+    })
+}
+
+/// Sign and issue the STS `AssumeRole` RPC call using Aliyun's classic RPC signature
+/// (`SignatureVersion=1.0`, `HMAC-SHA1`): https://www.alibabacloud.com/help/en/ram/developer-reference/api-sts-2015-04-01-assumerole
+async fn call_sts_assume_role(
+    http_client: &reqwest::Client,
+    access_key_id: &str,
+    access_key_secret: &str,
+    role_arn: &str,
+    role_session_name: &str,
+) -> crate::Result<StsAssumeRoleResponse> {
+    use rand::Rng;
+
+    let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let nonce = rand::rng().random::<u64>().to_string();
+
+    let mut params = vec![
+        ("AccessKeyId", access_key_id.to_string()),
+        ("Action", "AssumeRole".to_string()),
+        ("Format", "JSON".to_string()),
+        ("RoleArn", role_arn.to_string()),
+        ("RoleSessionName", role_session_name.to_string()),
+        ("SignatureMethod", "HMAC-SHA1".to_string()),
+        ("SignatureNonce", nonce),
+        ("SignatureVersion", "1.0".to_string()),
+        ("Timestamp", timestamp),
+        ("Version", "2015-04-01".to_string()),
+    ];
+    params.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+
+    let canonicalized_query = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", percent_encode(k), percent_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let string_to_sign =
+        format!("GET&{}&{}", percent_encode("/"), percent_encode(&canonicalized_query));
+    let signing_key = format!("{access_key_secret}&");
+    let signature = BASE64_STANDARD.encode(hmac_sha1(signing_key.as_bytes(), &string_to_sign));
+
+    let query = format!("{canonicalized_query}&Signature={}", percent_encode(&signature));
+    let response = http_client.get(format!("{STS_ENDPOINT}?{query}")).send().await?;
+
+    let status = response.status();
+    let body = response.bytes().await?;
+    if !status.is_success() {
+        return Err(Error::Other(format!(
+            "STS AssumeRole failed ({status}): {}",
+            String::from_utf8_lossy(&body)
+        )));
+    }
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+#[inline]
+fn hmac_sha1(key: &[u8], message: &str) -> Vec<u8> {
+    use hmac::{Hmac, Mac};
+    use sha1::Sha1;
+
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// The credential source [`crate::ClientBuilder`] falls back to when neither a
+/// [`CredentialProvider`] nor a fixed key pair is configured: a static key pair from
+/// `OSS_ACCESS_KEY_ID`/`OSS_ACCESS_KEY_SECRET`, then STS `AssumeRole` if `OSS_ROLE_ARN` is
+/// set, then the ECS/ACK instance metadata RAM role. Each stage is only attempted if the
+/// previous one fails, and the ECS/STS stages are cached and refreshed automatically by the
+/// [`RefreshingCredentialProvider`]s they're built from. `OSS_ROLE_ARN` is checked before the
+/// ECS RAM role so that configuring it to scope down to a specific role actually takes effect
+/// instead of being preempted by whatever role the instance metadata service hands back. This
+/// lets the crate run on an Alibaba Cloud instance without hard-coding long-lived keys.
+#[derive(Debug)]
+pub struct DefaultCredentialProviderChain {
+    http_client: reqwest::Client,
+}
+
+impl DefaultCredentialProviderChain {
+    pub fn new(http_client: reqwest::Client) -> Self {
+        Self { http_client }
+    }
+}
+
+impl CredentialProvider for DefaultCredentialProviderChain {
+    fn credential(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = crate::Result<ProvidedCredential>> + Send + '_>> {
+        Box::pin(async move {
+            if let Ok(credential) = EnvCredentialProvider::new().credential().await {
+                return Ok(credential);
+            }
+
+            if let Ok(role_arn) = std::env::var(ENV_ROLE_ARN) {
+                let access_key_id = std::env::var(ENV_ACCESS_KEY_ID).map_err(|_| {
+                    Error::InvalidArgument(format!("{ENV_ROLE_ARN} is set but {ENV_ACCESS_KEY_ID} is not"))
+                })?;
+                let access_key_secret = std::env::var(ENV_ACCESS_KEY_SECRET).map_err(|_| {
+                    Error::InvalidArgument(format!(
+                        "{ENV_ROLE_ARN} is set but {ENV_ACCESS_KEY_SECRET} is not"
+                    ))
+                })?;
+                let role_session_name =
+                    std::env::var(ENV_ROLE_SESSION_NAME).unwrap_or_else(|_| "ossify".to_string());
+                return sts_assume_role_credential_provider(
+                    self.http_client.clone(),
+                    access_key_id,
+                    access_key_secret,
+                    role_arn,
+                    role_session_name,
+                )
+                .credential()
+                .await;
+            }
+
+            // Only fall back to the ECS/ACK instance metadata RAM role once an explicit
+            // `OSS_ROLE_ARN` has had a chance to scope credentials down to a specific role —
+            // on an ECS instance this call can otherwise succeed unconditionally via
+            // `100.100.100.200` and silently preempt that least-privilege configuration.
+            let role_name = std::env::var(ENV_ECS_RAM_ROLE_NAME).ok();
+            if let Ok(credential) = ecs_ram_role_credential_provider(self.http_client.clone(), role_name)
+                .credential()
+                .await
+            {
+                return Ok(credential);
+            }
+
+            Err(Error::InvalidCredentials)
+        })
+    }
+}
+
 pub(crate) struct SignContext<'a, Q>
 where
     Q: Serialize,
@@ -62,6 +537,7 @@ impl Credential {
             query,
         }: SignContext<'_, Q>,
         query_auth_options: Option<QueryAuthOptions>,
+        now: DateTime<Utc>,
     ) -> Result<()>
     where
         Q: Serialize,
@@ -73,7 +549,7 @@ impl Credential {
         let x_sdk_client = format!("ossify/{version}");
 
         // Prepare x-oss-date and date
-        let datetime = Utc::now();
+        let datetime = now;
         let datetime_iso8601_str = datetime.format("%Y%m%dT%H%M%SZ").to_string();
         let datetime_rfc2822_str = datetime.to_rfc2822();
 
@@ -106,8 +582,10 @@ impl Credential {
         let mut canonical_additional_headers_str = Cow::Borrowed("");
         if !is_query_auth {
             let x_oss_content_sha256 = HeaderValue::from_static(UNSIGNED_PAYLOAD);
-            let x_sdk_client = HeaderValue::from_str(&x_sdk_client).context("parse x-sdk-client")?;
-            let x_oss_date = HeaderValue::from_str(&datetime_iso8601_str).expect("invalid x-oss-date");
+            let x_sdk_client =
+                HeaderValue::from_str(&x_sdk_client).context("parse x-sdk-client")?;
+            let x_oss_date =
+                HeaderValue::from_str(&datetime_iso8601_str).expect("invalid x-oss-date");
             let date_rfc2822 = HeaderValue::from_str(&datetime_rfc2822_str).expect("invalid date");
 
             let headers = request.headers_mut();
@@ -156,13 +634,19 @@ impl Credential {
         if is_query_auth {
             canonical_query = Cow::Owned(format!("{canonical_query}&x-oss-signature={signature}"));
         } else {
-            let mut credential_header =
-                format!("{SIGNATURE_VERSION} Credential={}/{scope}", self.access_key_id);
+            let mut credential_header = format!(
+                "{SIGNATURE_VERSION} Credential={}/{scope}",
+                self.access_key_id
+            );
             if !canonical_additional_headers_str.is_empty() {
-                write!(&mut credential_header, ",AdditionalHeaders={canonical_additional_headers_str}")?;
+                write!(
+                    &mut credential_header,
+                    ",AdditionalHeaders={canonical_additional_headers_str}"
+                )?;
             }
             write!(&mut credential_header, ",Signature={signature}")?;
-            let authorization = HeaderValue::from_str(&credential_header).expect("invalid Authorization");
+            let authorization =
+                HeaderValue::from_str(&credential_header).expect("invalid Authorization");
             let headers = request.headers_mut();
             headers.append(AUTHORIZATION, authorization);
         }
@@ -186,7 +670,7 @@ fn build_sign_path(bucket: Option<&str>, key: Option<&str>) -> String {
 }
 
 #[inline]
-fn build_scope(date_iso8601_str: &str, region: &str, product: &str) -> String {
+pub(crate) fn build_scope(date_iso8601_str: &str, region: &str, product: &str) -> String {
     format!("{date_iso8601_str}/{region}/{product}/aliyun_v4_request")
 }
 
@@ -210,7 +694,7 @@ pub(crate) fn hmac256(key: &[u8], message: &str) -> Result<Vec<u8>> {
     Ok(signature.into_bytes().to_vec())
 }
 
-fn calculate_signature(
+pub(crate) fn calculate_signature(
     access_key_secret: &str,
     date_iso8601_str: &str,
     region: &str,
@@ -241,8 +725,10 @@ fn canonical_headers(input: &HeaderMap, additional_headers: &HashSet<String>) ->
     }
     headers.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
 
-    Ok(headers.into_iter().fold(String::new(), |mut output, (k, v)| {
-        let _ = writeln!(output, "{k}:{v}");
-        output
-    }))
+    Ok(headers
+        .into_iter()
+        .fold(String::new(), |mut output, (k, v)| {
+            let _ = writeln!(output, "{k}:{v}");
+            output
+        }))
 }
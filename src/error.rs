@@ -72,10 +72,38 @@ pub enum Error {
 
     #[error("Deserialize header error: {0}")]
     DeHeaderError(String),
+
+    #[error("CRC64 checksum mismatch: expected {expected}, computed {computed}")]
+    Crc64Mismatch { expected: String, computed: String },
+
+    #[error("Content-MD5 checksum mismatch: expected {expected}, computed {computed}")]
+    ContentMd5Mismatch { expected: String, computed: String },
+
+    /// `304 Not Modified`: a conditional read's `if_none_match`/`if_modified_since` found the
+    /// object unchanged.
+    #[error("Not modified")]
+    NotModified,
+
+    /// `412 Precondition Failed`: a conditional read or copy's `if_match`/`if_unmodified_since`
+    /// found the object didn't meet the condition.
+    #[error("Precondition failed")]
+    PreconditionFailed,
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+impl Error {
+    /// The OSS error code carried by an [`Error::ApiError`], if any, so callers can match on
+    /// specific failure reasons (e.g. [`OssErrorCode::NoSuchKey`](response::OssErrorCode::NoSuchKey))
+    /// instead of string-comparing [`ErrorResponse::code`](response::ErrorResponse::code) by hand.
+    pub fn oss_error_code(&self) -> Option<response::OssErrorCode> {
+        match self {
+            Error::ApiError { message: Some(response), .. } => Some(response.oss_error_code()),
+            _ => None,
+        }
+    }
+}
+
 impl de::Error for Error {
     fn custom<T: fmt::Display>(msg: T) -> Self {
         Error::DeHeaderError(msg.to_string())
@@ -2,29 +2,52 @@ mod body;
 mod credential;
 mod error;
 pub mod ops;
+mod post_object;
+mod post_policy;
 mod query_auth_option;
 mod response;
 mod ser;
 mod utils;
 
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::time::Duration;
 
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use http::HeaderMap;
-use http::header::HOST;
+use http::header::{DATE, HOST, RETRY_AFTER};
+use http::{HeaderValue, StatusCode};
+use rand::Rng;
 use serde::Serialize;
 use tracing::trace;
 use url::Url;
 
 use self::body::MakeBody;
-use self::credential::{Credential, SignContext};
+use self::credential::SignContext;
+pub use self::credential::{
+    CredentialProvider, DefaultCredentialProviderChain, EnvCredentialProvider, ProvidedCredential,
+    RefreshingCredentialProvider, StaticCredentialProvider, ecs_ram_role_credential_provider,
+    sts_assume_role_credential_provider,
+};
 pub use self::error::Error;
 use self::error::Result;
+pub use self::post_object::PostObjectResponse;
+pub use self::post_policy::{PostPolicyBuilder, PresignedPost};
 pub use self::query_auth_option::{QueryAuthOptions, QueryAuthOptionsBuilder};
+pub use self::response::OssErrorCode;
 use self::response::ResponseProcessor;
 use self::utils::escape_path;
 
+/// A type-erased error, used by streaming body adapters (compression, client-side
+/// encryption, CRC64 verification, ...) so they aren't generic over the concrete error
+/// type of whatever stream they're wrapping.
+pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
 pub(crate) trait Ops: Sized {
     const PRODUCT: &'static str = "oss";
     const USE_BUCKET: bool = true;
@@ -60,6 +83,19 @@ pub(crate) trait Ops: Sized {
     fn body(&self) -> Option<&<Self::Body as MakeBody>::Body> {
         None
     }
+
+    /// Whether a failed request for this operation may be safely retried.
+    ///
+    /// Defaults to `true` for the idempotent methods (GET/HEAD/PUT/DELETE) and `false`
+    /// otherwise. Operations that are safe to retry despite using a non-idempotent method
+    /// (e.g. `CompleteMultipartUpload`, which OSS treats as idempotent for a given set of
+    /// parts) can override this to opt back in.
+    fn retryable(&self) -> bool {
+        matches!(
+            self.method(),
+            http::Method::GET | http::Method::HEAD | http::Method::PUT | http::Method::DELETE
+        )
+    }
 }
 
 pub(crate) trait Request<P> {
@@ -67,7 +103,13 @@ pub(crate) trait Request<P> {
 
     fn request(&self, ops: P) -> impl Future<Output = Result<Self::Response>>;
 
-    fn presign(
+    /// Build the URL for `ops` without executing it, signing it into the `Authorization`
+    /// header (`query_auth_options: None`) or into `x-oss-*` query parameters
+    /// (`query_auth_options: Some(_)`) instead.
+    ///
+    /// This is the low-level primitive behind [`Client::presign`]; most callers should use
+    /// that instead.
+    fn build_presigned_url(
         &self,
         ops: P,
         public: bool,
@@ -83,6 +125,62 @@ pub enum UrlStyle {
     CName,
 }
 
+/// Retry policy applied to retryable operations (see [`Ops::retryable`]).
+///
+/// Failed attempts are delayed using full-jitter exponential backoff:
+/// `delay = random(0, min(max_delay, base_delay * 2^(attempt - 1)))`, unless the response
+/// carries a `Retry-After` header, which takes precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// The maximum number of attempts made for a single request, including the first one.
+    /// A value of `1` disables retries entirely.
+    pub max_attempts: u32,
+    /// The base delay used for the exponential backoff calculation.
+    pub base_delay: Duration,
+    /// The maximum delay to wait between attempts, regardless of the attempt count.
+    pub max_delay: Duration,
+    /// Whether to randomize the computed delay (full jitter) instead of using it as-is.
+    pub jitter: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Compute the delay to wait before attempt number `attempt` (1-based; the delay
+    /// before the *second* attempt is `backoff(1, ..)`), honoring a `Retry-After` header
+    /// from the previous response when present.
+    fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+
+        // `1u32 << exponent` is only valid for `exponent` in `0..=31`; clamp to 31 and let
+        // `checked_mul` (rather than the shift itself) absorb any remaining overflow.
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .checked_mul(1u32 << exponent)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        if self.jitter {
+            let max_millis = delay.as_millis().min(u128::from(u64::MAX)) as u64;
+            Duration::from_millis(rand::rng().random_range(0..=max_millis))
+        } else {
+            delay
+        }
+    }
+}
+
 /// Configuration for the API client.
 /// Allows users to customize its behaviors.
 pub struct ClientConfig {
@@ -94,6 +192,8 @@ pub struct ClientConfig {
     pub url_style: UrlStyle,
     /// The URL style to use for the API client that uses public endpoint.
     pub public_url_style: UrlStyle,
+    /// The retry policy applied to retryable operations.
+    pub retry: RetryConfig,
 }
 
 impl Default for ClientConfig {
@@ -103,6 +203,7 @@ impl Default for ClientConfig {
             default_headers: http::HeaderMap::default(),
             url_style: UrlStyle::default(),
             public_url_style: UrlStyle::default(),
+            retry: RetryConfig::default(),
         }
     }
 }
@@ -119,14 +220,176 @@ pub struct Client {
     bucket: String,
     url_style: UrlStyle,
     public_url_style: UrlStyle,
-    credentials: Credential,
+    credentials: Arc<dyn CredentialProvider>,
+    retry_config: RetryConfig,
+    /// Correction applied to [`Client::now`], in milliseconds, learned from a server
+    /// `Date` header after OSS rejects a request as `RequestTimeTooSkewed`. Shared across
+    /// clones so the correction benefits every outstanding reference to this client.
+    clock_offset_ms: Arc<AtomicI64>,
 }
 
+/// The shortest validity window OSS accepts for a presigned URL.
+const MIN_PRESIGN_EXPIRES: Duration = Duration::from_secs(1);
+
+/// The longest validity window OSS accepts for a presigned URL (7 days), mirroring the
+/// limit OSS itself enforces on the `x-oss-expires` query-signature parameter.
+const MAX_PRESIGN_EXPIRES: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 impl Client {
     pub fn builder() -> ClientBuilder {
         ClientBuilder::new()
     }
 
+    /// The current time used to sign requests: the system clock, adjusted by whatever
+    /// correction [`Self::note_server_date`] has learned from OSS so far.
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now() + chrono::Duration::milliseconds(self.clock_offset_ms.load(Ordering::Relaxed))
+    }
+
+    /// Update the signing clock correction from a `Date` response header, so future
+    /// requests self-heal after OSS rejects one as `RequestTimeTooSkewed`.
+    fn note_server_date(&self, server_date: &HeaderValue) {
+        let Ok(server_date) = server_date.to_str() else {
+            return;
+        };
+        let Ok(server_date) = DateTime::parse_from_rfc2822(server_date) else {
+            return;
+        };
+        let offset_ms = (server_date.with_timezone(&Utc) - Utc::now()).num_milliseconds();
+        self.clock_offset_ms.store(offset_ms, Ordering::Relaxed);
+        trace!("Adjusted signing clock offset to {offset_ms}ms after a time-skew error");
+    }
+
+    /// Produce a time-limited, query-string-signed URL for `ops` (e.g. `GetObject`,
+    /// `PutObject`, `HeadObject`) instead of executing it, so the URL can be handed to a
+    /// browser or other client for direct upload/download without giving it the account
+    /// credentials. Any query parameters `ops` itself sets (e.g. `versionId`,
+    /// `response-content-disposition`) are folded into the canonical query alongside the
+    /// `x-oss-*` auth parameters, so the signature still matches when the URL is requested.
+    ///
+    /// `expires` is the duration the signature stays valid for, starting now; it must fall
+    /// within `1 second..=7 days`, the same window OSS enforces on `x-oss-expires`.
+    pub async fn presign<P>(&self, ops: P, expires: Duration) -> Result<Url>
+    where
+        P: Ops + Send + 'static,
+        P::Query: Serialize + Send,
+        P::Response: ResponseProcessor + Send,
+        P::Body: MakeBody + Send,
+    {
+        if !(MIN_PRESIGN_EXPIRES..=MAX_PRESIGN_EXPIRES).contains(&expires) {
+            return Err(Error::InvalidArgument(format!(
+                "presign expires must be between {MIN_PRESIGN_EXPIRES:?} and {MAX_PRESIGN_EXPIRES:?}, got {expires:?}"
+            )));
+        }
+
+        let query_auth_options = QueryAuthOptions::builder().x_oss_expires(expires.as_secs() as u32).build();
+        let url = self.build_presigned_url(ops, true, Some(query_auth_options)).await?;
+        Ok(Url::parse(&url)?)
+    }
+
+    /// Produce the fields an HTML form needs to upload an object directly to OSS via
+    /// `multipart/form-data`, instead of proxying the bytes through this service. The
+    /// browser's form must submit every field in [`PresignedPost::fields`] with the file
+    /// itself as the form's last field.
+    ///
+    /// `expires` is the duration the policy stays valid for, starting now; it must fall
+    /// within `1 second..=7 days`, the same window OSS enforces on presigned requests.
+    ///
+    /// The policy document (`policy.conditions` plus an `expiration`) is base64-encoded and
+    /// signed with the same `aliyun_v4`/date/region/product/`aliyun_v4_request` HMAC chain
+    /// used to sign headers and query-string auth, just with the base64 policy as the string
+    /// to sign instead of a canonical request.
+    pub async fn presign_post(
+        &self,
+        policy: PostPolicyBuilder,
+        expires: Duration,
+    ) -> Result<PresignedPost> {
+        let (url, fields) = self.sign_post_policy(policy, expires).await?;
+        Ok(PresignedPost { url, fields })
+    }
+
+    /// Upload `body` as `key` via the same signed `multipart/form-data` path
+    /// [`presign_post`](Self::presign_post) hands to browsers, instead of the header-signed
+    /// `PutObject` request `put_object` issues. Useful for exercising a `policy` (e.g. its
+    /// `content_length_range`) server-side before handing the same policy to a browser, or
+    /// for OSS-compatible endpoints that only accept the form-upload path.
+    ///
+    /// `expires` is the duration the policy stays valid for, starting now; it must fall
+    /// within `1 second..=7 days`, the same window OSS enforces on presigned requests.
+    pub async fn post_object(
+        &self,
+        key: impl Into<String>,
+        body: impl Into<Bytes>,
+        policy: PostPolicyBuilder,
+        expires: Duration,
+    ) -> Result<self::post_object::PostObjectResponse> {
+        let key = key.into();
+        let policy = policy.key(key.clone());
+        let (url, fields) = self.sign_post_policy(policy, expires).await?;
+
+        self::post_object::post_object(&self.http_client, url, fields, key, body.into()).await
+    }
+
+    /// Shared by [`presign_post`](Self::presign_post) and [`post_object`](Self::post_object):
+    /// turn a [`PostPolicyBuilder`] into the signed `multipart/form-data` fields and target
+    /// URL an upload (browser-submitted or not) needs.
+    async fn sign_post_policy(&self, policy: PostPolicyBuilder, expires: Duration) -> Result<(String, HashMap<String, String>)> {
+        if !(MIN_PRESIGN_EXPIRES..=MAX_PRESIGN_EXPIRES).contains(&expires) {
+            return Err(Error::InvalidArgument(format!(
+                "presign_post expires must be between {MIN_PRESIGN_EXPIRES:?} and {MAX_PRESIGN_EXPIRES:?}, got {expires:?}"
+            )));
+        }
+
+        let credential = self.credentials.credential().await?;
+
+        let datetime = self.now();
+        let datetime_iso8601_str = datetime.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_iso8601_str = &datetime_iso8601_str[..8];
+        let scope = self::credential::build_scope(date_iso8601_str, &self.region, "oss");
+        let credential_field = format!("{}/{scope}", credential.access_key_id);
+
+        let policy_duration = chrono::Duration::from_std(expires)
+            .map_err(|err| Error::InvalidArgument(format!("invalid presign_post expires: {err}")))?;
+        let expiration = datetime + policy_duration;
+
+        let mut fields = policy.fields;
+        let mut conditions = policy.conditions;
+        conditions.push(serde_json::json!({ "bucket": self.bucket }));
+        let signature_version = self::credential::SIGNATURE_VERSION;
+        conditions.push(serde_json::json!({ "x-oss-signature-version": signature_version }));
+        conditions.push(serde_json::json!({ "x-oss-credential": credential_field }));
+        conditions.push(serde_json::json!({ "x-oss-date": datetime_iso8601_str }));
+        if let Some(token) = &credential.security_token {
+            conditions.push(serde_json::json!({ "x-oss-security-token": token }));
+            fields.insert("x-oss-security-token".to_string(), token.clone());
+        }
+
+        let policy_document = serde_json::json!({
+            "expiration": expiration.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "conditions": conditions,
+        });
+        let policy_base64 = BASE64_STANDARD.encode(serde_json::to_vec(&policy_document)?);
+
+        let signature = hex::encode(self::credential::calculate_signature(
+            &credential.access_key_secret,
+            date_iso8601_str,
+            &self.region,
+            "oss",
+            &policy_base64,
+        )?);
+
+        fields.insert("policy".to_string(), policy_base64);
+        fields.insert("x-oss-signature-version".to_string(), signature_version.to_string());
+        fields.insert("x-oss-credential".to_string(), credential_field);
+        fields.insert("x-oss-date".to_string(), datetime_iso8601_str);
+        fields.insert("x-oss-signature".to_string(), signature);
+
+        let (host, path) = self.build_url(Some(Cow::Borrowed(self.bucket.as_str())), None, true);
+        let url = format!("{}://{host}{path}", self.raw_public_scheme);
+
+        Ok((url, fields))
+    }
+
     fn build_url<'a>(
         &'a self,
         bucket: Option<Cow<'a, str>>,
@@ -174,7 +437,7 @@ impl Client {
         (host, path)
     }
 
-    fn prepare_request<P>(
+    async fn prepare_request<P>(
         &self,
         ops: P,
         public: bool,
@@ -227,13 +490,31 @@ impl Client {
         };
 
         // Authenticate the request
-        self.credentials
-            .auth_to(&mut request, sign_context, query_auth_options)?;
+        let credential: self::credential::Credential = self.credentials.credential().await?.into();
+        credential.auth_to(&mut request, sign_context, query_auth_options, self.now())?;
 
         Ok(request)
     }
 }
 
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Whether `err` carries an OSS error code the retry loop considers transient, beyond
+/// what [`is_retryable_status`] already recognizes purely from the HTTP status (e.g.
+/// `RequestTimeTooSkewed`, which OSS reports as `403 Forbidden`).
+fn is_retryable_error(err: &Error) -> bool {
+    err.oss_error_code().is_some_and(|code| code.is_retryable())
+}
+
+/// Parse a `Retry-After` header value, which per RFC 9110 is either a number of seconds
+/// or an HTTP date. Only the delay-seconds form is honored; an HTTP-date is ignored so we
+/// fall back to the configured backoff instead.
+fn parse_retry_after(value: &HeaderValue) -> Option<Duration> {
+    value.to_str().ok()?.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
 impl<P> Request<P> for Client
 where
     P: Ops + Send + 'static,
@@ -244,23 +525,88 @@ where
     type Response = <P::Response as ResponseProcessor>::Output;
 
     async fn request(&self, ops: P) -> Result<Self::Response> {
-        let request = self.prepare_request(ops, false, None)?;
-
-        // Send the request
-        trace!("Sending request: {request:?}");
-        let resp = self.http_client.execute(request).await?;
+        let retryable = ops.retryable();
+        let request = self.prepare_request(ops, false, None).await?;
+
+        let mut attempt: u32 = 1;
+        loop {
+            // If the request body can't be cloned (e.g. a streaming body), we can only try
+            // once: consume the original request and return whatever happens.
+            let Some(attempt_request) = request.try_clone() else {
+                trace!("Sending request: {request:?}");
+                let resp = self.http_client.execute(request).await?;
+                return P::Response::from_response(resp).await;
+            };
+
+            trace!("Sending request (attempt {attempt}): {attempt_request:?}");
+            let outcome = self.http_client.execute(attempt_request).await;
+            let can_retry = retryable && attempt < self.retry_config.max_attempts;
+
+            let resp = match outcome {
+                Ok(resp) => resp,
+                Err(err) => {
+                    if !can_retry || !(err.is_connect() || err.is_timeout() || err.is_request()) {
+                        return Err(err.into());
+                    }
+                    let delay = self.retry_config.backoff(attempt, None);
+                    trace!("Retrying request after {delay:?} (attempt {attempt} failed: {err})");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                },
+            };
+
+            let retry_after = resp.headers().get(RETRY_AFTER).and_then(parse_retry_after);
+
+            if is_retryable_status(resp.status()) {
+                if !can_retry {
+                    return P::Response::from_response(resp).await;
+                }
+                let delay = self.retry_config.backoff(attempt, retry_after);
+                trace!(
+                    "Retrying request after {delay:?} (attempt {attempt} failed with status {})",
+                    resp.status()
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            // `is_retryable_status` only looks at the HTTP status, which doesn't catch
+            // `RequestTimeTooSkewed` (reported as a plain `403 Forbidden`). Peek the body of
+            // a client-error response so a clock-skew error both corrects `Client::now` via
+            // `note_server_date` and gets retried.
+            if can_retry && resp.status().is_client_error() {
+                let status = resp.status();
+                let date_header = resp.headers().get(DATE).cloned();
+                let text = resp.text().await?;
+                let error = self::response::error_from_text(status, &text)?;
+
+                if !is_retryable_error(&error) {
+                    return Err(error);
+                }
 
-        // Parse the response
-        P::Response::from_response(resp).await
+                if let Some(date_header) = &date_header {
+                    self.note_server_date(date_header);
+                }
+                let delay = self.retry_config.backoff(attempt, retry_after);
+                trace!("Retrying request after {delay:?} (attempt {attempt} failed: {error})");
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            return P::Response::from_response(resp).await;
+        }
     }
 
-    async fn presign(
+    async fn build_presigned_url(
         &self,
         ops: P,
         public: bool,
         query_auth_options: Option<QueryAuthOptions>,
     ) -> Result<String> {
-        let request = self.prepare_request(ops, public, query_auth_options)?;
+        let request = self.prepare_request(ops, public, query_auth_options).await?;
 
         let sign_url = request.url().to_string();
         Ok(sign_url)
@@ -276,6 +622,7 @@ pub struct ClientBuilder {
     access_key_id: Option<String>,
     access_key_secret: Option<String>,
     security_token: Option<String>,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
 }
 
 impl ClientBuilder {
@@ -289,6 +636,7 @@ impl ClientBuilder {
             access_key_id: None,
             access_key_secret: None,
             security_token: None,
+            credential_provider: None,
         }
     }
 
@@ -334,6 +682,15 @@ impl ClientBuilder {
         self
     }
 
+    /// Source credentials from `provider` instead of the fixed
+    /// `access_key_id`/`access_key_secret`/`security_token` set on this builder, e.g. to
+    /// auto-refresh temporary STS credentials via [`RefreshingCredentialProvider`].
+    /// Takes precedence over the fixed fields if both are set.
+    pub fn credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credential_provider = Some(Arc::new(provider));
+        self
+    }
+
     /// Set the HTTP timeout for requests
     pub fn http_timeout(mut self, timeout: Duration) -> Self {
         self.config.http_timeout = timeout;
@@ -358,6 +715,12 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the retry policy applied to retryable operations
+    pub fn retry_config(mut self, retry: RetryConfig) -> Self {
+        self.config.retry = retry;
+        self
+    }
+
     /// Build the Client with the configured parameters
     pub fn build(self) -> Result<Client> {
         // Validate required fields
@@ -370,19 +733,30 @@ impl ClientBuilder {
         let bucket = self
             .bucket
             .ok_or_else(|| Error::InvalidArgument("bucket is required".to_string()))?;
-        let access_key_id = self
-            .access_key_id
-            .ok_or_else(|| Error::InvalidArgument("access_key_id is required".to_string()))?;
-        let access_key_secret = self
-            .access_key_secret
-            .ok_or_else(|| Error::InvalidArgument("access_key_secret is required".to_string()))?;
-
         // Build HTTP client
         let http_client = reqwest::Client::builder()
             .default_headers(self.config.default_headers)
             .timeout(self.config.http_timeout)
             .build()?;
 
+        let credential_provider = match self.credential_provider {
+            Some(provider) => provider,
+            None => match (self.access_key_id, self.access_key_secret) {
+                (Some(access_key_id), Some(access_key_secret)) => {
+                    Arc::new(StaticCredentialProvider::new(ProvidedCredential {
+                        access_key_id,
+                        access_key_secret,
+                        security_token: self.security_token,
+                        expires_at: None,
+                    })) as Arc<dyn CredentialProvider>
+                },
+                // Neither a provider nor a fixed key pair was set: fall back to the default
+                // chain (env vars, then ECS/ACK instance metadata, then STS AssumeRole) so
+                // the client can run on an Alibaba Cloud instance without hard-coded keys.
+                _ => Arc::new(DefaultCredentialProviderChain::new(http_client.clone())),
+            },
+        };
+
         // Parse endpoint URL
         let endpoint_url = Url::parse(&endpoint)?;
         let raw_internal_host = endpoint_url.host_str().ok_or(Error::MissingHost)?.to_owned();
@@ -397,13 +771,6 @@ impl ClientBuilder {
             .to_owned();
         let raw_public_scheme = public_endpoint_url.scheme().to_owned();
 
-        // Build credentials
-        let credentials = Credential {
-            security_token: self.security_token,
-            access_key_id,
-            access_key_secret,
-        };
-
         Ok(Client {
             region,
             bucket,
@@ -413,8 +780,10 @@ impl ClientBuilder {
             raw_public_scheme,
             url_style: self.config.url_style,
             public_url_style: self.config.public_url_style,
-            credentials,
+            credentials: credential_provider,
             http_client,
+            retry_config: self.config.retry,
+            clock_offset_ms: Arc::new(AtomicI64::new(0)),
         })
     }
 }
@@ -1,5 +1,6 @@
 use std::future::Future;
 
+use futures::{Stream, TryStreamExt, stream};
 use http::Method;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_with::skip_serializing_none;
@@ -7,6 +8,7 @@ use serde_with::skip_serializing_none;
 use crate::body::NoneBody;
 use crate::error::Result;
 use crate::ops::Owner;
+use crate::ops::pagination::paginate;
 use crate::response::BodyResponseProcessor;
 use crate::{Client, Ops, Prepared, QueryAuthOptions, Request};
 
@@ -161,6 +163,24 @@ pub trait ListObjectsOps {
         params: Option<ListObjectsV2Params>,
     ) -> impl Future<Output = Result<ListObjectsResult>>;
 
+    /// Walk every page of a ListObjectsV2 listing, following `next_continuation_token`
+    /// until `is_truncated` is false. Each page is only fetched once the consumer polls
+    /// for it, so arbitrarily large buckets can be walked with bounded memory.
+    fn list_objects_pages(
+        &self,
+        params: Option<ListObjectsV2Params>,
+    ) -> impl Stream<Item = Result<ListObjectsResult>> + Send + 'static;
+
+    /// Walk every object across all pages of a ListObjectsV2 listing, auto-paginating via
+    /// `next_continuation_token` as the stream is polled. Prefer [`list_objects_pages`]
+    /// over this if you also need `common_prefixes` from each page.
+    ///
+    /// [`list_objects_pages`]: ListObjectsOps::list_objects_pages
+    fn list_objects_stream(
+        &self,
+        params: Option<ListObjectsV2Params>,
+    ) -> impl Stream<Item = Result<ObjectSummary>> + Send + 'static;
+
     /// Presign list objects operation
     fn presign_list_objects(
         &self,
@@ -176,6 +196,34 @@ impl ListObjectsOps for Client {
         self.request(ops).await
     }
 
+    fn list_objects_pages(
+        &self,
+        params: Option<ListObjectsV2Params>,
+    ) -> impl Stream<Item = Result<ListObjectsResult>> + Send + 'static {
+        let client = self.clone();
+        let base_params = params.unwrap_or_default();
+        paginate(
+            base_params.continuation_token.clone(),
+            move |continuation_token| {
+                let client = client.clone();
+                let mut params = base_params.clone();
+                params.continuation_token = continuation_token;
+                async move { client.list_objects(Some(params)).await }
+            },
+            |page| page.is_truncated,
+            |page| page.next_continuation_token.clone(),
+        )
+    }
+
+    fn list_objects_stream(
+        &self,
+        params: Option<ListObjectsV2Params>,
+    ) -> impl Stream<Item = Result<ObjectSummary>> + Send + 'static {
+        self.list_objects_pages(params)
+            .map_ok(|page| stream::iter(page.contents.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
     async fn presign_list_objects(
         &self,
         public: bool,
@@ -183,6 +231,6 @@ impl ListObjectsOps for Client {
         query_auth_options: QueryAuthOptions,
     ) -> Result<String> {
         let ops = ListObjects::new(params);
-        self.presign(ops, public, Some(query_auth_options)).await
+        self.build_presigned_url(ops, public, Some(query_auth_options)).await
     }
 }
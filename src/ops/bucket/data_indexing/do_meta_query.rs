@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::future::Future;
+use std::str::FromStr;
 
+use futures::{Stream, TryStreamExt, stream};
 use http::Method;
 use serde::de::IntoDeserializer;
 use serde::{Deserialize, Serialize};
@@ -8,18 +10,23 @@ use serde_with::skip_serializing_none;
 
 use crate::body::XMLBody;
 use crate::error::Result;
+use crate::ops::pagination::paginate;
 use crate::response::BodyResponseProcessor;
 use crate::ser::OnlyKeyField;
 use crate::{Client, Ops, Request};
 
 /// Query mode
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(remote = "DoMetaQueryMode")]
 #[serde(rename_all = "lowercase")]
 pub enum DoMetaQueryMode {
     /// Basic query
     Basic,
     /// Semantic query (vector search)
     Semantic,
+    /// A mode value OSS returned that this client version doesn't know about yet
+    #[serde(skip_deserializing, skip_serializing)]
+    UnknownValue(String),
 }
 
 impl Default for DoMetaQueryMode {
@@ -33,18 +40,55 @@ impl AsRef<str> for DoMetaQueryMode {
         match self {
             DoMetaQueryMode::Basic => "basic",
             DoMetaQueryMode::Semantic => "semantic",
+            DoMetaQueryMode::UnknownValue(s) => s.as_str(),
+        }
+    }
+}
+
+impl Serialize for DoMetaQueryMode {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            DoMetaQueryMode::UnknownValue(s) => serializer.serialize_str(s),
+            known => Self::serialize(known, serializer),
         }
     }
 }
 
+impl<'de> Deserialize<'de> for DoMetaQueryMode {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::deserialize(s.as_str().into_deserializer()).unwrap_or(DoMetaQueryMode::UnknownValue(s)))
+    }
+}
+
+impl FromStr for DoMetaQueryMode {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self::deserialize(s.into_deserializer()).unwrap_or_else(|_: serde::de::value::Error| {
+            DoMetaQueryMode::UnknownValue(s.to_string())
+        }))
+    }
+}
+
 /// Sort order
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(remote = "SortOrder")]
 #[serde(rename_all = "lowercase")]
 pub enum SortOrder {
     /// Ascending order
     Asc,
     /// Descending order
     Desc,
+    /// A sort order value OSS returned that this client version doesn't know about yet
+    #[serde(skip_deserializing, skip_serializing)]
+    UnknownValue(String),
 }
 
 impl Default for SortOrder {
@@ -58,18 +102,54 @@ impl AsRef<str> for SortOrder {
         match self {
             SortOrder::Asc => "asc",
             SortOrder::Desc => "desc",
+            SortOrder::UnknownValue(s) => s.as_str(),
+        }
+    }
+}
+
+impl Serialize for SortOrder {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            SortOrder::UnknownValue(s) => serializer.serialize_str(s),
+            known => Self::serialize(known, serializer),
         }
     }
 }
 
+impl<'de> Deserialize<'de> for SortOrder {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::deserialize(s.as_str().into_deserializer()).unwrap_or(SortOrder::UnknownValue(s)))
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self::deserialize(s.into_deserializer())
+            .unwrap_or_else(|_: serde::de::value::Error| SortOrder::UnknownValue(s.to_string())))
+    }
+}
+
 /// Media type (used for vector search)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(remote = "MediaType")]
 #[serde(rename_all = "lowercase")]
 pub enum MediaType {
     Image,
     Video,
     Audio,
     Document,
+    /// A media type value OSS returned that this client version doesn't know about yet
+    #[serde(skip_deserializing, skip_serializing)]
+    UnknownValue(String),
 }
 
 impl AsRef<str> for MediaType {
@@ -79,12 +159,45 @@ impl AsRef<str> for MediaType {
             MediaType::Video => "video",
             MediaType::Audio => "audio",
             MediaType::Document => "document",
+            MediaType::UnknownValue(s) => s.as_str(),
         }
     }
 }
 
+impl Serialize for MediaType {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            MediaType::UnknownValue(s) => serializer.serialize_str(s),
+            known => Self::serialize(known, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaType {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::deserialize(s.as_str().into_deserializer()).unwrap_or(MediaType::UnknownValue(s)))
+    }
+}
+
+impl FromStr for MediaType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self::deserialize(s.into_deserializer())
+            .unwrap_or_else(|_: serde::de::value::Error| MediaType::UnknownValue(s.to_string())))
+    }
+}
+
 /// Aggregation operation type
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(remote = "AggregationOperation")]
 #[serde(rename_all = "lowercase")]
 pub enum AggregationOperation {
     /// Calculate sum
@@ -99,6 +212,9 @@ pub enum AggregationOperation {
     Count,
     /// Group statistics
     Group,
+    /// An aggregation operation OSS returned that this client version doesn't know about yet
+    #[serde(skip_deserializing, skip_serializing)]
+    UnknownValue(String),
 }
 
 impl AsRef<str> for AggregationOperation {
@@ -110,10 +226,43 @@ impl AsRef<str> for AggregationOperation {
             AggregationOperation::Min => "min",
             AggregationOperation::Count => "count",
             AggregationOperation::Group => "group",
+            AggregationOperation::UnknownValue(s) => s.as_str(),
         }
     }
 }
 
+impl Serialize for AggregationOperation {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            AggregationOperation::UnknownValue(s) => serializer.serialize_str(s),
+            known => Self::serialize(known, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AggregationOperation {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::deserialize(s.as_str().into_deserializer()).unwrap_or(AggregationOperation::UnknownValue(s)))
+    }
+}
+
+impl FromStr for AggregationOperation {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self::deserialize(s.into_deserializer()).unwrap_or_else(|_: serde::de::value::Error| {
+            AggregationOperation::UnknownValue(s.to_string())
+        }))
+    }
+}
+
 /// Aggregation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -139,6 +288,43 @@ pub struct AggregationResult {
     pub groups: Vec<AggregationGroup>,
 }
 
+impl AggregationResult {
+    /// The aggregation operation that produced this result, reusing the
+    /// forward-compatible [`AggregationOperation`] enum so an operation this client
+    /// doesn't recognize yet comes back as [`AggregationOperation::UnknownValue`]
+    /// instead of failing to parse.
+    pub fn operation(&self) -> AggregationOperation {
+        self.operation.parse().unwrap()
+    }
+
+    /// Parse `value` as an `f64`, for `sum`/`avg`/`max`/`min` results.
+    pub fn as_f64(&self) -> Result<f64> {
+        let value = self
+            .value
+            .as_deref()
+            .ok_or_else(|| crate::error::Error::InvalidArgument("aggregation result has no value".to_string()))?;
+        value
+            .parse()
+            .map_err(|_| crate::error::Error::InvalidArgument(format!("aggregation value is not a number: {value}")))
+    }
+
+    /// Parse `value` as a `u64`, for `count` results.
+    pub fn as_u64(&self) -> Result<u64> {
+        let value = self
+            .value
+            .as_deref()
+            .ok_or_else(|| crate::error::Error::InvalidArgument("aggregation result has no value".to_string()))?;
+        value
+            .parse()
+            .map_err(|_| crate::error::Error::InvalidArgument(format!("aggregation value is not a number: {value}")))
+    }
+
+    /// Build a group value -> count map from `groups`, for `group` results.
+    pub fn group_map(&self) -> HashMap<String, u64> {
+        self.groups.iter().map(|group| (group.value.clone(), group.count)).collect()
+    }
+}
+
 /// Aggregated group results
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -212,10 +398,20 @@ pub struct FileInfo {
     /// File size (bytes)
     pub size: u64,
     /// File last modified time
+    #[cfg(not(feature = "chrono"))]
     pub file_modified_time: Option<String>,
+    /// File last modified time
+    #[cfg(feature = "chrono")]
+    #[serde(deserialize_with = "deserialize_optional_rfc3339")]
+    pub file_modified_time: Option<chrono::DateTime<chrono::Utc>>,
     /// Object last modified time
+    #[cfg(not(feature = "chrono"))]
     #[serde(rename = "OSSObjectLastModifiedTime")]
     pub oss_object_last_modified_time: Option<String>,
+    /// Object last modified time
+    #[cfg(feature = "chrono")]
+    #[serde(rename = "OSSObjectLastModifiedTime", deserialize_with = "deserialize_optional_rfc3339")]
+    pub oss_object_last_modified_time: Option<chrono::DateTime<chrono::Utc>>,
     /// ETag
     #[serde(rename = "ETag")]
     pub etag: Option<String>,
@@ -223,18 +419,33 @@ pub struct FileInfo {
     #[serde(rename = "OSSCRC64")]
     pub oss_crc64: Option<String>,
     /// Creation time
+    #[cfg(not(feature = "chrono"))]
     pub produce_time: Option<String>,
+    /// Creation time
+    #[cfg(feature = "chrono")]
+    #[serde(deserialize_with = "deserialize_optional_rfc3339")]
+    pub produce_time: Option<chrono::DateTime<chrono::Utc>>,
     /// Content type
     pub content_type: Option<String>,
     /// Media type
     pub media_type: Option<String>,
     /// Longitude and latitude coordinates
+    #[cfg(not(feature = "geo"))]
     pub lat_long: Option<String>,
+    /// Longitude and latitude coordinates
+    #[cfg(feature = "geo")]
+    #[serde(deserialize_with = "deserialize_optional_lat_long")]
+    pub lat_long: Option<(f64, f64)>,
     /// Title
     pub title: Option<String>,
     /// Expiration time
+    #[cfg(not(feature = "chrono"))]
     #[serde(rename = "OSSExpiration")]
     pub oss_expiration: Option<String>,
+    /// Expiration time
+    #[cfg(feature = "chrono")]
+    #[serde(rename = "OSSExpiration", deserialize_with = "deserialize_optional_rfc3339")]
+    pub oss_expiration: Option<chrono::DateTime<chrono::Utc>>,
     /// Cache control
     pub cache_control: Option<String>,
     /// Content description
@@ -422,6 +633,43 @@ where
     }
 }
 
+/// Parse an RFC3339 timestamp, treating an absent or empty string as `None` and only
+/// erroring on a non-empty, malformed one.
+#[cfg(feature = "chrono")]
+fn deserialize_optional_rfc3339<'de, D>(de: D) -> std::result::Result<Option<chrono::DateTime<chrono::Utc>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let opt = Option::<String>::deserialize(de)?;
+    match opt.as_deref() {
+        None | Some("") => Ok(None),
+        Some(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .map(|dt| Some(dt.with_timezone(&chrono::Utc)))
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+/// Parse a `"lat,long"` string into typed coordinates, treating an absent or empty
+/// string as `None` and only erroring on a non-empty, malformed one.
+#[cfg(feature = "geo")]
+fn deserialize_optional_lat_long<'de, D>(de: D) -> std::result::Result<Option<(f64, f64)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let opt = Option::<String>::deserialize(de)?;
+    match opt.as_deref() {
+        None | Some("") => Ok(None),
+        Some(s) => {
+            let (lat, long) = s
+                .split_once(',')
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid lat_long value: {s}")))?;
+            let lat: f64 = lat.trim().parse().map_err(serde::de::Error::custom)?;
+            let long: f64 = long.trim().parse().map_err(serde::de::Error::custom)?;
+            Ok(Some((lat, long)))
+        }
+    }
+}
+
 /// MetaQuery response
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -495,6 +743,28 @@ pub trait DataIndexingOperations {
         body: MetaQueryBody,
         query: DoMetaQueryParams,
     ) -> impl Future<Output = Result<MetaQueryResponse>>;
+
+    /// Walk every page of a DoMetaQuery listing, following `next_token` until it comes
+    /// back `None`. Each page is only fetched once the consumer polls for it, so
+    /// arbitrarily large result sets can be walked with bounded memory.
+    fn do_meta_query_pages(
+        &self,
+        mode: DoMetaQueryMode,
+        body: MetaQueryBody,
+        query: DoMetaQueryParams,
+    ) -> impl Stream<Item = Result<MetaQueryResponse>> + Send + 'static;
+
+    /// Walk every file across all pages of a DoMetaQuery listing, auto-paginating via
+    /// `next_token` as the stream is polled. Prefer [`do_meta_query_pages`] over this if
+    /// you also need `aggregations` from each page.
+    ///
+    /// [`do_meta_query_pages`]: DataIndexingOperations::do_meta_query_pages
+    fn do_meta_query_stream(
+        &self,
+        mode: DoMetaQueryMode,
+        body: MetaQueryBody,
+        query: DoMetaQueryParams,
+    ) -> impl Stream<Item = Result<FileInfo>> + Send + 'static;
 }
 
 impl DataIndexingOperations for Client {
@@ -507,136 +777,183 @@ impl DataIndexingOperations for Client {
         let ops = DoMetaQuery { mode, body, query };
         self.request(ops).await
     }
+
+    fn do_meta_query_pages(
+        &self,
+        mode: DoMetaQueryMode,
+        base_body: MetaQueryBody,
+        query: DoMetaQueryParams,
+    ) -> impl Stream<Item = Result<MetaQueryResponse>> + Send + 'static {
+        let client = self.clone();
+        paginate(
+            base_body.next_token.clone(),
+            move |next_token| {
+                let client = client.clone();
+                let mut body = base_body.clone();
+                body.next_token = next_token;
+                let mode = mode.clone();
+                let query = query.clone();
+                async move { client.do_meta_query(mode, body, query).await }
+            },
+            |page| page.next_token.is_some(),
+            |page| page.next_token.clone(),
+        )
+    }
+
+    fn do_meta_query_stream(
+        &self,
+        mode: DoMetaQueryMode,
+        body: MetaQueryBody,
+        query: DoMetaQueryParams,
+    ) -> impl Stream<Item = Result<FileInfo>> + Send + 'static {
+        self.do_meta_query_pages(mode, body, query)
+            .map_ok(|page| stream::iter(page.files.into_iter().map(Ok)))
+            .try_flatten()
+    }
 }
 
 // =============================================================================
 // Convenience builder and helper functions
 // =============================================================================
 
-/// Query builder for constructing complex query conditions
-#[derive(Debug, Clone)]
-pub struct QueryBuilder {
-    conditions: Vec<QueryCondition>,
+/// Boolean operation combining the children of a [`QueryNode::Group`]
+#[derive(Debug, Clone, Copy)]
+pub enum BoolOp {
+    And,
+    Or,
+    Not,
+}
+
+impl AsRef<str> for BoolOp {
+    fn as_ref(&self) -> &str {
+        match self {
+            BoolOp::And => "and",
+            BoolOp::Or => "or",
+            BoolOp::Not => "not",
+        }
+    }
 }
 
-/// Query condition
+/// A node in a query condition tree: either a single field comparison, or a boolean
+/// group of sub-nodes (and/or/not).
 #[derive(Debug, Clone)]
-pub struct QueryCondition {
-    pub field: String,
-    pub operation: String,
-    pub value: String,
+pub enum QueryNode {
+    /// A single field comparison, e.g. `size gt 1024`
+    Leaf {
+        field: String,
+        operation: String,
+        value: String,
+    },
+    /// A boolean combination of sub-nodes, e.g. `(a or b) and not c`
+    Group {
+        operation: BoolOp,
+        children: Vec<QueryNode>,
+    },
+}
+
+impl QueryNode {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            QueryNode::Leaf { field, operation, value } => serde_json::json!({
+                "Field": field,
+                "Operation": operation,
+                "Value": value
+            }),
+            QueryNode::Group { operation, children } => serde_json::json!({
+                "Operation": operation.as_ref(),
+                "SubQueries": children.iter().map(QueryNode::to_json).collect::<Vec<_>>()
+            }),
+        }
+    }
+}
+
+/// Query builder for constructing complex, arbitrarily nested query conditions
+#[derive(Debug, Clone, Default)]
+pub struct QueryBuilder {
+    nodes: Vec<QueryNode>,
 }
 
 impl QueryBuilder {
     pub fn new() -> Self {
-        Self {
-            conditions: Vec::new(),
-        }
+        Self { nodes: Vec::new() }
     }
 
-    pub fn eq(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
-        self.conditions.push(QueryCondition {
+    fn leaf(mut self, field: impl Into<String>, operation: &str, value: impl Into<String>) -> Self {
+        self.nodes.push(QueryNode::Leaf {
             field: field.into(),
-            operation: "eq".to_string(),
+            operation: operation.to_string(),
             value: value.into(),
         });
         self
     }
 
-    pub fn gt(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
-        self.conditions.push(QueryCondition {
-            field: field.into(),
-            operation: "gt".to_string(),
-            value: value.into(),
-        });
-        self
+    pub fn eq(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.leaf(field, "eq", value)
     }
 
-    pub fn gte(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
-        self.conditions.push(QueryCondition {
-            field: field.into(),
-            operation: "gte".to_string(),
-            value: value.into(),
-        });
-        self
+    pub fn gt(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.leaf(field, "gt", value)
     }
 
-    pub fn lt(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
-        self.conditions.push(QueryCondition {
-            field: field.into(),
-            operation: "lt".to_string(),
-            value: value.into(),
-        });
-        self
+    pub fn gte(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.leaf(field, "gte", value)
     }
 
-    pub fn lte(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
-        self.conditions.push(QueryCondition {
-            field: field.into(),
-            operation: "lte".to_string(),
-            value: value.into(),
-        });
-        self
+    pub fn lt(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.leaf(field, "lt", value)
     }
 
-    pub fn prefix(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
-        self.conditions.push(QueryCondition {
-            field: field.into(),
-            operation: "prefix".to_string(),
-            value: value.into(),
-        });
-        self
+    pub fn lte(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.leaf(field, "lte", value)
     }
 
-    pub fn r#match(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
-        self.conditions.push(QueryCondition {
-            field: field.into(),
-            operation: "match".to_string(),
-            value: value.into(),
-        });
+    pub fn prefix(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.leaf(field, "prefix", value)
+    }
+
+    pub fn r#match(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.leaf(field, "match", value)
+    }
+
+    fn group(mut self, operation: BoolOp, build: impl FnOnce(QueryBuilder) -> QueryBuilder) -> Self {
+        let children = build(QueryBuilder::new()).nodes;
+        self.nodes.push(QueryNode::Group { operation, children });
         self
     }
 
+    /// Combine the conditions built by `build` with AND into a single sub-group.
+    pub fn and(self, build: impl FnOnce(QueryBuilder) -> QueryBuilder) -> Self {
+        self.group(BoolOp::And, build)
+    }
+
+    /// Combine the conditions built by `build` with OR into a single sub-group.
+    pub fn or(self, build: impl FnOnce(QueryBuilder) -> QueryBuilder) -> Self {
+        self.group(BoolOp::Or, build)
+    }
+
+    /// Negate the conditions built by `build` as a single sub-group.
+    pub fn not(self, build: impl FnOnce(QueryBuilder) -> QueryBuilder) -> Self {
+        self.group(BoolOp::Not, build)
+    }
+
     pub fn build(self) -> Result<String> {
-        if self.conditions.is_empty() {
+        if self.nodes.is_empty() {
             return Err(crate::error::Error::InvalidArgument(
                 "Query conditions cannot be empty".to_string(),
             ));
         }
 
-        if self.conditions.len() == 1 {
-            let condition = &self.conditions[0];
-            let query = serde_json::json!({
-                "Field": condition.field,
-                "Operation": condition.operation,
-                "Value": condition.value
-            });
-            Ok(query.to_string())
+        let mut nodes = self.nodes;
+        let root = if nodes.len() == 1 {
+            nodes.pop().unwrap()
         } else {
-            let sub_queries: Vec<_> = self
-                .conditions
-                .iter()
-                .map(|c| {
-                    serde_json::json!({
-                        "Field": c.field,
-                        "Operation": c.operation,
-                        "Value": c.value
-                    })
-                })
-                .collect();
-
-            let query = serde_json::json!({
-                "Operation": "and",
-                "SubQueries": sub_queries
-            });
-            Ok(query.to_string())
-        }
-    }
-}
+            QueryNode::Group {
+                operation: BoolOp::And,
+                children: nodes,
+            }
+        };
 
-impl Default for QueryBuilder {
-    fn default() -> Self {
-        Self::new()
+        Ok(root.to_json().to_string())
     }
 }
 
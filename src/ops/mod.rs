@@ -1,5 +1,6 @@
 pub mod bucket;
 pub mod object;
+pub(crate) mod pagination;
 pub mod service;
 
 use serde::Deserialize;
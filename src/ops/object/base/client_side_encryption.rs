@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bytes::{Bytes, BytesMut};
+use futures::Stream;
+use rand::RngCore;
+
+use crate::BoxError;
+use crate::body::BoxedByteStream;
+use crate::error::{Error, Result};
+
+/// Size of a sealed chunk on the wire: a full plaintext [`CHUNK_SIZE`] chunk plus its
+/// 16-byte GCM tag. [`ClientSideDecryptor`] re-chunks the ciphertext stream to this size
+/// before decrypting, since HTTP body chunk boundaries don't otherwise line up with it.
+const SEALED_CHUNK_SIZE: usize = CHUNK_SIZE + 16;
+
+/// Size of each plaintext chunk sealed by [`ClientSideEncryptStream`]. Every sealed chunk
+/// grows by 16 bytes (the GCM tag), so the ciphertext stream is always larger than the
+/// plaintext by `ceil(len / CHUNK_SIZE) * 16` bytes.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Associated data bound to a non-final chunk's GCM tag.
+const NOT_LAST_CHUNK_AAD: &[u8] = &[0];
+/// Associated data bound to the final chunk's GCM tag. Because only the true final chunk
+/// is sealed with this AAD, a ciphertext truncated at a chunk boundary fails to decrypt
+/// past the cut point instead of silently yielding a short object.
+const LAST_CHUNK_AAD: &[u8] = &[1];
+
+/// Algorithm identifier stored alongside the wrapped key, identifying both the content
+/// cipher and the key-wrap cipher (both are plain AES-256-GCM here).
+const AES_GCM_ALG: &str = "AES/GCM/NoPadding";
+
+/// A 256-bit client-side master key used to wrap/unwrap the per-object content-encryption
+/// key (CEK) for [`PutObjectOptions::client_side_encryption_key`](super::PutObjectOptions).
+///
+/// Wrapped so that it can't accidentally end up in a `Debug` output and so the key
+/// material is wiped from memory once it's no longer needed, mirroring
+/// [`SseCustomerKey`](super::SseCustomerKey).
+#[derive(Clone)]
+pub struct ClientSideEncryptionKey([u8; 32]);
+
+impl ClientSideEncryptionKey {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+}
+
+impl std::fmt::Debug for ClientSideEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ClientSideEncryptionKey(..)")
+    }
+}
+
+impl Drop for ClientSideEncryptionKey {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.0.zeroize();
+    }
+}
+
+/// Generates the per-object content-encryption key, wraps it under the caller's master
+/// key, and exposes the result both as `x-oss-meta-*` entries and as a [`Stream`] adapter
+/// that seals the plaintext body in fixed-size chunks.
+pub(crate) struct ClientSideEncryptor {
+    cek: [u8; 32],
+    nonce_prefix: [u8; 8],
+    wrapped_cek: Vec<u8>,
+}
+
+impl ClientSideEncryptor {
+    /// Generate a fresh CEK and base nonce, wrapping the CEK under `master_key`.
+    pub(crate) fn generate(master_key: &ClientSideEncryptionKey) -> Result<Self> {
+        let mut cek = [0u8; 32];
+        rand::rng().fill_bytes(&mut cek);
+
+        let mut nonce_prefix = [0u8; 8];
+        rand::rng().fill_bytes(&mut nonce_prefix);
+
+        let wrapped_cek = wrap_cek(&master_key.0, &cek)?;
+
+        Ok(Self { cek, nonce_prefix, wrapped_cek })
+    }
+
+    /// The `x-oss-meta-client-side-encryption-*` entries a future `GetObject` path needs
+    /// to recover the CEK and base nonce and reverse the encryption.
+    pub(crate) fn metadata(&self) -> HashMap<String, String> {
+        let mut meta = HashMap::with_capacity(4);
+        meta.insert("client-side-encryption-key".to_string(), BASE64.encode(&self.wrapped_cek));
+        meta.insert("client-side-encryption-start".to_string(), BASE64.encode(self.nonce_prefix));
+        meta.insert("client-side-encryption-cek-alg".to_string(), AES_GCM_ALG.to_string());
+        meta.insert("client-side-encryption-wrap-alg".to_string(), AES_GCM_ALG.to_string());
+        meta.insert("client-side-encryption-part-size".to_string(), CHUNK_SIZE.to_string());
+        meta
+    }
+
+    /// Wrap `inner` so that it yields AES-256-GCM-sealed chunks instead of plaintext.
+    pub(crate) fn into_stream(self, inner: BoxedByteStream) -> ClientSideEncryptStream {
+        ClientSideEncryptStream {
+            inner,
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.cek)),
+            nonce_prefix: self.nonce_prefix,
+            counter: 0,
+            buffer: BytesMut::new(),
+            inner_done: false,
+            finished: false,
+        }
+    }
+
+    /// Expose the sealing primitive directly instead of behind a [`Stream`], for a caller
+    /// (multipart upload) that already drives its own chunked reads one part at a time.
+    pub(crate) fn into_sealer(self) -> ClientSideEncryptorSealer {
+        ClientSideEncryptorSealer {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.cek)),
+            nonce_prefix: self.nonce_prefix,
+            counter: 0,
+        }
+    }
+}
+
+/// Wrap `cek` under `master_key` with AES-256-GCM, returning `nonce || ciphertext || tag`.
+fn wrap_cek(master_key: &[u8; 32], cek: &[u8; 32]) -> Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(master_key));
+
+    let mut nonce_bytes = [0u8; 12];
+    rand::rng().fill_bytes(&mut nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), cek.as_slice())
+        .map_err(|_| Error::Other("failed to wrap client-side encryption key".to_string()))?;
+
+    let mut wrapped = Vec::with_capacity(nonce_bytes.len() + ciphertext.len());
+    wrapped.extend_from_slice(&nonce_bytes);
+    wrapped.extend_from_slice(&ciphertext);
+    Ok(wrapped)
+}
+
+/// Unwrap a CEK previously sealed by [`wrap_cek`], given `wrapped = nonce || ciphertext || tag`.
+fn unwrap_cek(master_key: &[u8; 32], wrapped: &[u8]) -> Result<[u8; 32]> {
+    if wrapped.len() < 12 {
+        return Err(Error::Other("client-side encryption key metadata is truncated".to_string()));
+    }
+    let (nonce_bytes, ciphertext) = wrapped.split_at(12);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(master_key));
+    let cek = cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| Error::Other("failed to unwrap client-side encryption key".to_string()))?;
+
+    cek.try_into().map_err(|_| Error::Other("unwrapped client-side encryption key has the wrong length".to_string()))
+}
+
+/// Derive the per-chunk nonce: the random 8-byte base nonce followed by a 32-bit
+/// big-endian chunk counter, for a full 96-bit AES-GCM nonce.
+fn chunk_nonce(nonce_prefix: &[u8; 8], counter: u32) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..8].copy_from_slice(nonce_prefix);
+    nonce[8..].copy_from_slice(&counter.to_be_bytes());
+    nonce
+}
+
+/// Recovers the per-object content-encryption key from the `x-oss-meta-client-side-*`
+/// metadata a [`ClientSideEncryptor`] attached on upload, and exposes a [`Stream`] adapter
+/// that reverses its chunked sealing.
+pub(crate) struct ClientSideDecryptor {
+    cek: [u8; 32],
+    nonce_prefix: [u8; 8],
+}
+
+impl ClientSideDecryptor {
+    /// Unwrap the CEK under `master_key`, given the base64 `client-side-encryption-key`
+    /// and `client-side-encryption-start` metadata values [`ClientSideEncryptor::metadata`]
+    /// attached to the object.
+    pub(crate) fn from_metadata(master_key: &ClientSideEncryptionKey, wrapped_cek_b64: &str, nonce_prefix_b64: &str) -> Result<Self> {
+        let wrapped_cek = BASE64
+            .decode(wrapped_cek_b64)
+            .map_err(|err| Error::Other(format!("invalid client-side encryption key metadata: {err}")))?;
+        let nonce_prefix = BASE64
+            .decode(nonce_prefix_b64)
+            .map_err(|err| Error::Other(format!("invalid client-side encryption nonce metadata: {err}")))?;
+        let nonce_prefix: [u8; 8] = nonce_prefix
+            .try_into()
+            .map_err(|_| Error::Other("client-side encryption nonce metadata has the wrong length".to_string()))?;
+
+        let cek = unwrap_cek(&master_key.0, &wrapped_cek)?;
+
+        Ok(Self { cek, nonce_prefix })
+    }
+
+    /// Decrypt an entire buffered GetObject body at once.
+    pub(crate) fn decode_bytes(&self, data: &[u8]) -> Result<Bytes> {
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.cek));
+        let mut out = BytesMut::with_capacity(data.len());
+
+        let mut chunks = data.chunks(SEALED_CHUNK_SIZE).peekable();
+        let mut counter = 0u32;
+        while let Some(chunk) = chunks.next() {
+            let last = chunks.peek().is_none();
+            let plaintext = open_chunk(&cipher, &self.nonce_prefix, counter, chunk, last)
+                .map_err(|err| Error::Other(err.to_string()))?;
+            out.extend_from_slice(&plaintext);
+            counter = counter.checked_add(1).ok_or_else(|| {
+                Error::Other("client-side encryption chunk counter overflowed".to_string())
+            })?;
+        }
+
+        Ok(out.freeze())
+    }
+
+    /// Wrap `inner` so that it yields decrypted plaintext chunks instead of ciphertext.
+    pub(crate) fn into_stream(self, inner: BoxedByteStream) -> ClientSideDecryptStream {
+        ClientSideDecryptStream {
+            inner,
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.cek)),
+            nonce_prefix: self.nonce_prefix,
+            counter: 0,
+            buffer: BytesMut::new(),
+            inner_done: false,
+            finished: false,
+        }
+    }
+}
+
+/// Seal a single chunk, incrementing `*counter` on success. Shared by
+/// [`ClientSideEncryptStream`] and [`ClientSideEncryptorSealer`], the `Stream`- and
+/// buffer-driven encryptors respectively.
+fn seal_chunk(cipher: &Aes256Gcm, nonce_prefix: &[u8; 8], counter: &mut u32, plaintext: &[u8], last: bool) -> Result<Bytes, BoxError> {
+    let nonce = chunk_nonce(nonce_prefix, *counter);
+    *counter = counter
+        .checked_add(1)
+        .ok_or_else(|| Error::Other("client-side encryption chunk counter overflowed".to_string()))?;
+
+    let aad = if last { LAST_CHUNK_AAD } else { NOT_LAST_CHUNK_AAD };
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+        .map_err(|_| Error::Other("failed to seal client-side encryption chunk".to_string()))?;
+
+    Ok(Bytes::from(ciphertext))
+}
+
+/// Seals plaintext in fixed-size [`CHUNK_SIZE`] chunks on demand, for a caller (multipart
+/// upload) that drives its own chunked reads instead of polling a [`Stream`]. Every part
+/// uploaded with chunks sealed this way is independently decryptable by
+/// [`ClientSideDecryptor`], as long as non-final parts are a whole multiple of
+/// [`CHUNK_SIZE`].
+///
+/// Built by [`ClientSideEncryptor::into_sealer`].
+pub(crate) struct ClientSideEncryptorSealer {
+    cipher: Aes256Gcm,
+    nonce_prefix: [u8; 8],
+    counter: u32,
+}
+
+impl ClientSideEncryptorSealer {
+    /// Seal `plaintext` in [`CHUNK_SIZE`] chunks, sealing the last chunk with
+    /// [`LAST_CHUNK_AAD`] only when `last` is set (the caller has reached the end of the
+    /// object). `plaintext` must be a whole multiple of [`CHUNK_SIZE`] unless `last` is set.
+    pub(crate) fn seal(&mut self, plaintext: &[u8], last: bool) -> Result<Bytes> {
+        let mut out = BytesMut::with_capacity(plaintext.len() + 16 * plaintext.len().div_ceil(CHUNK_SIZE).max(1));
+
+        if plaintext.is_empty() {
+            if last {
+                let sealed = seal_chunk(&self.cipher, &self.nonce_prefix, &mut self.counter, &[], true)
+                    .map_err(|err| Error::Other(err.to_string()))?;
+                out.extend_from_slice(&sealed);
+            }
+            return Ok(out.freeze());
+        }
+
+        let mut chunks = plaintext.chunks(CHUNK_SIZE).peekable();
+        while let Some(chunk) = chunks.next() {
+            let is_last_chunk = last && chunks.peek().is_none();
+            let sealed = seal_chunk(&self.cipher, &self.nonce_prefix, &mut self.counter, chunk, is_last_chunk)
+                .map_err(|err| Error::Other(err.to_string()))?;
+            out.extend_from_slice(&sealed);
+        }
+
+        Ok(out.freeze())
+    }
+
+    /// The chunk size every non-final [`seal`](Self::seal) call must be a whole multiple of.
+    pub(crate) const fn chunk_size() -> usize {
+        CHUNK_SIZE
+    }
+}
+
+/// Open a single sealed chunk, choosing the AAD that matches whether it's the final chunk
+/// (mirroring [`seal_chunk`]'s choice on the way in).
+fn open_chunk(cipher: &Aes256Gcm, nonce_prefix: &[u8; 8], counter: u32, ciphertext: &[u8], last: bool) -> Result<Bytes, BoxError> {
+    let nonce = chunk_nonce(nonce_prefix, counter);
+    let aad = if last { LAST_CHUNK_AAD } else { NOT_LAST_CHUNK_AAD };
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| Box::new(Error::Other("failed to open client-side encryption chunk".to_string())) as BoxError)?;
+    Ok(Bytes::from(plaintext))
+}
+
+/// A [`Stream`] adapter that encrypts a `PutObject` body with AES-256-GCM using a
+/// chunked-AEAD "STREAM" construction before it leaves the process: each fixed-size
+/// plaintext chunk is sealed with a nonce derived from the base nonce plus a 32-bit
+/// chunk counter, and the final chunk is bound to distinct associated data so a
+/// ciphertext truncated at a chunk boundary is detected rather than silently accepted.
+///
+/// Built by [`ClientSideEncryptor::into_stream`]; never constructed directly.
+pub(crate) struct ClientSideEncryptStream {
+    inner: BoxedByteStream,
+    cipher: Aes256Gcm,
+    nonce_prefix: [u8; 8],
+    counter: u32,
+    buffer: BytesMut,
+    inner_done: bool,
+    finished: bool,
+}
+
+impl Stream for ClientSideEncryptStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.finished {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            if this.buffer.len() >= CHUNK_SIZE {
+                let chunk = this.buffer.split_to(CHUNK_SIZE).freeze();
+                return Poll::Ready(Some(seal_chunk(&this.cipher, &this.nonce_prefix, &mut this.counter, &chunk, false)));
+            }
+
+            if this.inner_done {
+                this.finished = true;
+                let chunk = std::mem::take(&mut this.buffer).freeze();
+                return Poll::Ready(Some(seal_chunk(&this.cipher, &this.nonce_prefix, &mut this.counter, &chunk, true)));
+            }
+
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => this.buffer.extend_from_slice(&item),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => this.inner_done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A [`Stream`] adapter that decrypts a streamed GetObject body, reversing
+/// [`ClientSideEncryptStream`]: ciphertext is re-chunked to [`SEALED_CHUNK_SIZE`]
+/// (independent of whatever chunk boundaries the HTTP body arrived in) and each sealed
+/// chunk is opened with a nonce derived from the base nonce plus a 32-bit chunk counter.
+///
+/// Built by [`ClientSideDecryptor::into_stream`]; never constructed directly.
+pub(crate) struct ClientSideDecryptStream {
+    inner: BoxedByteStream,
+    cipher: Aes256Gcm,
+    nonce_prefix: [u8; 8],
+    counter: u32,
+    buffer: BytesMut,
+    inner_done: bool,
+    finished: bool,
+}
+
+impl Stream for ClientSideDecryptStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.finished {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            if this.buffer.len() >= SEALED_CHUNK_SIZE {
+                let chunk = this.buffer.split_to(SEALED_CHUNK_SIZE).freeze();
+                let counter = this.counter;
+                this.counter += 1;
+                return Poll::Ready(Some(open_chunk(&this.cipher, &this.nonce_prefix, counter, &chunk, false)));
+            }
+
+            if this.inner_done {
+                this.finished = true;
+                let chunk = std::mem::take(&mut this.buffer).freeze();
+                return Poll::Ready(Some(open_chunk(&this.cipher, &this.nonce_prefix, this.counter, &chunk, true)));
+            }
+
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => this.buffer.extend_from_slice(&item),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => this.inner_done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
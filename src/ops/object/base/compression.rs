@@ -0,0 +1,265 @@
+use std::io::Write;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use flate2::write::{DeflateDecoder, GzDecoder, GzEncoder};
+use futures::Stream;
+use zstd::stream::write::{Decoder as ZstdDecoder, Encoder as ZstdEncoder};
+
+use crate::BoxError;
+use crate::body::BoxedByteStream;
+use crate::error::{Error, Result};
+
+/// Compression algorithm applied to a `PutObject` body before upload. See
+/// [`PutObjectOptions::compression`](super::PutObjectOptions).
+#[derive(Debug, Clone, Copy)]
+pub enum Compression {
+    /// Gzip, via `flate2`. `level` ranges 0 (store) through 9 (best compression).
+    Gzip { level: u32 },
+    /// Zstandard. `level` ranges roughly 1 (fastest) through 22 (best compression).
+    Zstd { level: i32 },
+}
+
+impl Compression {
+    /// The `Content-Encoding` value this compression algorithm corresponds to.
+    pub(crate) fn content_encoding(&self) -> &'static str {
+        match self {
+            Compression::Gzip { .. } => "gzip",
+            Compression::Zstd { .. } => "zstd",
+        }
+    }
+
+    fn into_encoder(self) -> Result<Encoder> {
+        Ok(match self {
+            Compression::Gzip { level } => Encoder::Gzip(GzEncoder::new(Vec::new(), flate2::Compression::new(level))),
+            Compression::Zstd { level } => {
+                Encoder::Zstd(ZstdEncoder::new(Vec::new(), level).map_err(|err| Error::Other(err.to_string()))?)
+            },
+        })
+    }
+
+    /// Wrap `inner` so that it yields `self`-compressed chunks instead of plaintext.
+    pub(crate) fn into_stream(self, inner: BoxedByteStream) -> Result<CompressStream> {
+        Ok(CompressStream {
+            inner,
+            encoder: Some(self.into_encoder()?),
+            inner_done: false,
+        })
+    }
+}
+
+enum Encoder {
+    Gzip(GzEncoder<Vec<u8>>),
+    Zstd(ZstdEncoder<'static, Vec<u8>>),
+}
+
+impl Encoder {
+    /// Feed `data` through the encoder, returning whatever compressed bytes it flushed
+    /// out as a result (possibly empty, if the encoder is still buffering internally).
+    fn write(&mut self, data: &[u8]) -> std::io::Result<Bytes> {
+        match self {
+            Encoder::Gzip(enc) => {
+                enc.write_all(data)?;
+                Ok(Bytes::from(std::mem::take(enc.get_mut())))
+            },
+            Encoder::Zstd(enc) => {
+                enc.write_all(data)?;
+                Ok(Bytes::from(std::mem::take(enc.get_mut())))
+            },
+        }
+    }
+
+    /// Flush the final container trailer (e.g. the gzip CRC32/size footer).
+    fn finish(self) -> std::io::Result<Bytes> {
+        match self {
+            Encoder::Gzip(enc) => Ok(Bytes::from(enc.finish()?)),
+            Encoder::Zstd(enc) => Ok(Bytes::from(enc.finish()?)),
+        }
+    }
+}
+
+/// A [`Stream`] adapter that gzip/zstd-compresses a `PutObject` body on the fly. Because
+/// the compressed length isn't known up front, this is always driven as a streaming body
+/// rather than one with a fixed `Content-Length`, same as any other [`StreamBody`].
+///
+/// Built by [`Compression::into_stream`]; never constructed directly.
+pub(crate) struct CompressStream {
+    inner: BoxedByteStream,
+    encoder: Option<Encoder>,
+    inner_done: bool,
+}
+
+impl Stream for CompressStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.inner_done {
+                let Some(encoder) = this.encoder.take() else {
+                    return Poll::Ready(None);
+                };
+                return match encoder.finish() {
+                    Ok(trailer) if trailer.is_empty() => Poll::Ready(None),
+                    Ok(trailer) => Poll::Ready(Some(Ok(trailer))),
+                    Err(err) => Poll::Ready(Some(Err(Box::new(err) as BoxError))),
+                };
+            }
+
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    let encoder = this.encoder.as_mut().expect("encoder already finished");
+                    match encoder.write(&chunk) {
+                        Ok(compressed) if compressed.is_empty() => continue,
+                        Ok(compressed) => return Poll::Ready(Some(Ok(compressed))),
+                        Err(err) => return Poll::Ready(Some(Err(Box::new(err) as BoxError))),
+                    }
+                },
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => this.inner_done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Size of the internal ring buffer `brotli`'s streaming decompressor works in.
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+enum Decoder {
+    Gzip(GzDecoder<Vec<u8>>),
+    Deflate(DeflateDecoder<Vec<u8>>),
+    Zstd(ZstdDecoder<'static, Vec<u8>>),
+    Brotli(Box<brotli::DecompressorWriter<Vec<u8>>>),
+}
+
+impl Decoder {
+    /// Build the decoder matching a `Content-Encoding` value, or `None` if the body is
+    /// already plaintext (header absent or `identity`). Any other, unrecognized encoding is
+    /// an error rather than a silent pass-through, since returning the still-compressed
+    /// bytes as if they were plaintext would be worse than failing loudly.
+    fn for_content_encoding(content_encoding: &str) -> Result<Option<Self>> {
+        Ok(match content_encoding {
+            "identity" => None,
+            "gzip" => Some(Decoder::Gzip(GzDecoder::new(Vec::new()))),
+            "deflate" => Some(Decoder::Deflate(DeflateDecoder::new(Vec::new()))),
+            "zstd" => Some(Decoder::Zstd(
+                ZstdDecoder::new(Vec::new()).map_err(|err| Error::Other(err.to_string()))?,
+            )),
+            "br" => Some(Decoder::Brotli(Box::new(brotli::DecompressorWriter::new(Vec::new(), BROTLI_BUFFER_SIZE)))),
+            other => return Err(Error::Other(format!("unsupported Content-Encoding for transparent decompression: {other}"))),
+        })
+    }
+
+    /// Feed `data` through the decoder, returning whatever decompressed bytes it flushed
+    /// out as a result (possibly empty, if the decoder is still buffering internally).
+    fn write(&mut self, data: &[u8]) -> std::io::Result<Bytes> {
+        match self {
+            Decoder::Gzip(dec) => {
+                dec.write_all(data)?;
+                Ok(Bytes::from(std::mem::take(dec.get_mut())))
+            },
+            Decoder::Deflate(dec) => {
+                dec.write_all(data)?;
+                Ok(Bytes::from(std::mem::take(dec.get_mut())))
+            },
+            Decoder::Zstd(dec) => {
+                dec.write_all(data)?;
+                Ok(Bytes::from(std::mem::take(dec.get_mut())))
+            },
+            Decoder::Brotli(dec) => {
+                dec.write_all(data)?;
+                Ok(Bytes::from(std::mem::take(dec.get_mut())))
+            },
+        }
+    }
+
+    /// Flush any output the decoder was still holding back internally.
+    fn finish(self) -> std::io::Result<Bytes> {
+        match self {
+            Decoder::Gzip(dec) => Ok(Bytes::from(dec.finish()?)),
+            Decoder::Deflate(dec) => Ok(Bytes::from(dec.finish()?)),
+            Decoder::Zstd(dec) => Ok(Bytes::from(dec.finish()?)),
+            Decoder::Brotli(mut dec) => {
+                dec.flush()?;
+                Ok(Bytes::from(std::mem::take(dec.get_mut())))
+            },
+        }
+    }
+}
+
+/// Decompress an entire buffered GetObject body at once, given the response's
+/// `Content-Encoding` header. Returns `None` (the original bytes should be used as-is) for
+/// an absent/`identity` encoding, and errors on anything else this crate can't decode
+/// (gzip, deflate, zstd and br are supported).
+pub(crate) fn decode_bytes(content_encoding: &str, data: &[u8]) -> Result<Option<Bytes>> {
+    let Some(mut decoder) = Decoder::for_content_encoding(content_encoding)? else {
+        return Ok(None);
+    };
+    let mut out = decoder.write(data)?.to_vec();
+    out.extend_from_slice(&decoder.finish()?);
+    Ok(Some(Bytes::from(out)))
+}
+
+/// Wrap a streamed GetObject body so it yields decompressed chunks, given the response's
+/// `Content-Encoding` header. Returns `inner` unchanged for an absent/`identity` encoding,
+/// and errors on anything else this crate can't decode (gzip, deflate, zstd and br are
+/// supported).
+pub(crate) fn decompress_stream(
+    content_encoding: &str,
+    inner: BoxedByteStream,
+) -> Result<BoxedByteStream> {
+    match Decoder::for_content_encoding(content_encoding)? {
+        Some(decoder) => Ok(Box::pin(DecompressStream {
+            inner,
+            decoder: Some(decoder),
+            inner_done: false,
+        })),
+        None => Ok(inner),
+    }
+}
+
+/// A [`Stream`] adapter that transparently gzip/zstd-decompresses a streamed GetObject
+/// body. Built by [`decompress_stream`]; never constructed directly.
+struct DecompressStream {
+    inner: BoxedByteStream,
+    decoder: Option<Decoder>,
+    inner_done: bool,
+}
+
+impl Stream for DecompressStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.inner_done {
+                let Some(decoder) = this.decoder.take() else {
+                    return Poll::Ready(None);
+                };
+                return match decoder.finish() {
+                    Ok(trailer) if trailer.is_empty() => Poll::Ready(None),
+                    Ok(trailer) => Poll::Ready(Some(Ok(trailer))),
+                    Err(err) => Poll::Ready(Some(Err(Box::new(err) as BoxError))),
+                };
+            }
+
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    let decoder = this.decoder.as_mut().expect("decoder already finished");
+                    match decoder.write(&chunk) {
+                        Ok(decompressed) if decompressed.is_empty() => continue,
+                        Ok(decompressed) => return Poll::Ready(Some(Ok(decompressed))),
+                        Err(err) => return Poll::Ready(Some(Err(Box::new(err) as BoxError))),
+                    }
+                },
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => this.inner_done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -1,15 +1,64 @@
+use std::collections::HashMap;
 use std::future::Future;
 
+use chrono::{DateTime, Utc};
+use heck::ToKebabCase;
 use http::header::HeaderName;
 use http::{HeaderMap, Method};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
 
+use super::SseCustomerKey;
 use crate::body::ZeroBody;
-use crate::error::Result;
-use crate::response::BodyResponseProcessor;
-use crate::utils::escape_path;
+use crate::error::{Error, Result};
+use crate::response::BodyHeaderResponseProcessor;
+use crate::utils::{escape_path, format_http_date};
 use crate::{Client, Ops, Prepared, Request};
 
+/// Whether the destination object's metadata and content headers (cache control,
+/// content type, etc.) are copied from the source object or rebuilt from the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetadataDirective {
+    /// Carry the source object's metadata and content headers over unchanged. OSS
+    /// ignores any content headers or [`CopyObjectOptions::user_metadata`] entries set
+    /// on the request in this mode.
+    #[serde(rename = "COPY")]
+    Copy,
+    /// Rebuild the destination object's metadata and content headers entirely from the
+    /// request, discarding the source's.
+    #[serde(rename = "REPLACE")]
+    Replace,
+}
+
+impl AsRef<str> for MetadataDirective {
+    fn as_ref(&self) -> &str {
+        match self {
+            MetadataDirective::Copy => "COPY",
+            MetadataDirective::Replace => "REPLACE",
+        }
+    }
+}
+
+/// Whether the destination object's tags are copied from the source object or replaced
+/// by [`CopyObjectOptions::tagging`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaggingDirective {
+    /// Carry the source object's tags over unchanged.
+    #[serde(rename = "Copy")]
+    Copy,
+    /// Replace the destination object's tags with [`CopyObjectOptions::tagging`].
+    #[serde(rename = "Replace")]
+    Replace,
+}
+
+impl AsRef<str> for TaggingDirective {
+    fn as_ref(&self) -> &str {
+        match self {
+            TaggingDirective::Copy => "Copy",
+            TaggingDirective::Replace => "Replace",
+        }
+    }
+}
+
 /// CopyObject options for headers
 #[derive(Debug, Clone, Default)]
 pub struct CopyObjectOptions {
@@ -24,7 +73,11 @@ pub struct CopyObjectOptions {
     /// The object copy condition. If the time specified in the request is earlier than the time when the object is modified, OSS copies the object
     pub copy_source_if_unmodified_since: Option<String>,
     /// The method that is used to set the metadata of the destination object
-    pub metadata_directive: Option<String>,
+    pub metadata_directive: Option<MetadataDirective>,
+    /// User-defined metadata to attach to the destination object as `x-oss-meta-<key>`
+    /// headers. Only takes effect when `metadata_directive` is
+    /// [`MetadataDirective::Replace`].
+    pub user_metadata: HashMap<String, String>,
     /// The cache control of the destination object
     pub cache_control: Option<String>,
     /// The content disposition of the destination object
@@ -46,7 +99,16 @@ pub struct CopyObjectOptions {
     /// The tag of the destination object
     pub tagging: Option<String>,
     /// The method that is used to configure tags for the destination object
-    pub tagging_directive: Option<String>,
+    pub tagging_directive: Option<TaggingDirective>,
+    /// The customer-provided SSE-C key to encrypt the destination object with.
+    pub sse_customer_key: Option<SseCustomerKey>,
+    /// The customer-provided SSE-C key the source object was encrypted with, needed to
+    /// read it during the copy.
+    pub copy_source_sse_customer_key: Option<SseCustomerKey>,
+    /// If set, compare this value against the `x-oss-hash-crc64ecma` the copy response
+    /// reports (e.g. from a prior `HeadObject` of the source) and fail with
+    /// [`Error::Crc64Mismatch`](crate::error::Error::Crc64Mismatch) on a mismatch.
+    pub expected_crc64: Option<u64>,
 }
 
 impl CopyObjectOptions {
@@ -72,21 +134,56 @@ impl CopyObjectOptions {
         self
     }
 
-    /// Set copy source if modified since condition
+    /// Set copy source if modified since condition as a raw header value. Prefer
+    /// [`copy_source_if_modified_since_at`](Self::copy_source_if_modified_since_at), which
+    /// formats the timestamp the way OSS requires; this is an escape hatch for callers who
+    /// already have a pre-formatted HTTP-date string.
     pub fn copy_source_if_modified_since(mut self, time: impl Into<String>) -> Self {
         self.copy_source_if_modified_since = Some(time.into());
         self
     }
 
-    /// Set copy source if unmodified since condition
+    /// Set copy source if modified since condition, formatting `time` as the RFC 7231
+    /// HTTP-date OSS expects instead of requiring the caller to get the format right.
+    pub fn copy_source_if_modified_since_at(mut self, time: DateTime<Utc>) -> Self {
+        self.copy_source_if_modified_since = Some(format_http_date(time));
+        self
+    }
+
+    /// Set copy source if unmodified since condition as a raw header value. Prefer
+    /// [`copy_source_if_unmodified_since_at`](Self::copy_source_if_unmodified_since_at),
+    /// which formats the timestamp the way OSS requires; this is an escape hatch for
+    /// callers who already have a pre-formatted HTTP-date string.
     pub fn copy_source_if_unmodified_since(mut self, time: impl Into<String>) -> Self {
         self.copy_source_if_unmodified_since = Some(time.into());
         self
     }
 
+    /// Set copy source if unmodified since condition, formatting `time` as the RFC 7231
+    /// HTTP-date OSS expects instead of requiring the caller to get the format right.
+    pub fn copy_source_if_unmodified_since_at(mut self, time: DateTime<Utc>) -> Self {
+        self.copy_source_if_unmodified_since = Some(format_http_date(time));
+        self
+    }
+
     /// Set metadata directive
-    pub fn metadata_directive(mut self, directive: impl Into<String>) -> Self {
-        self.metadata_directive = Some(directive.into());
+    pub fn metadata_directive(mut self, directive: MetadataDirective) -> Self {
+        self.metadata_directive = Some(directive);
+        self
+    }
+
+    /// Attach a user-defined metadata entry, emitted as an `x-oss-meta-<key>` header on
+    /// the destination object. Only takes effect when `metadata_directive` is
+    /// [`MetadataDirective::Replace`].
+    pub fn user_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.user_metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Attach several user-defined metadata entries at once. See
+    /// [`user_metadata`](Self::user_metadata).
+    pub fn user_metadata_map(mut self, metadata: HashMap<String, String>) -> Self {
+        self.user_metadata.extend(metadata);
         self
     }
 
@@ -151,8 +248,28 @@ impl CopyObjectOptions {
     }
 
     /// Set tagging directive
-    pub fn tagging_directive(mut self, directive: impl Into<String>) -> Self {
-        self.tagging_directive = Some(directive.into());
+    pub fn tagging_directive(mut self, directive: TaggingDirective) -> Self {
+        self.tagging_directive = Some(directive);
+        self
+    }
+
+    /// Encrypt the destination object with the customer-provided 256-bit AES key (SSE-C).
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Decrypt the source object with the customer-provided 256-bit AES key (SSE-C) it
+    /// was uploaded with.
+    pub fn copy_source_sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.copy_source_sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Verify the copy by comparing `expected` against the `x-oss-hash-crc64ecma` the
+    /// copy response reports.
+    pub fn expected_crc64(mut self, expected: u64) -> Self {
+        self.expected_crc64 = Some(expected);
         self
     }
 }
@@ -164,6 +281,32 @@ impl CopyObjectOptions {
         source_key: String,
         source_version_id: Option<String>,
     ) -> Result<HeaderMap> {
+        if self.copy_source_if_match.is_some() && self.copy_source_if_none_match.is_some() {
+            return Err(Error::InvalidArgument(
+                "copy_source_if_match and copy_source_if_none_match are mutually exclusive".to_string(),
+            ));
+        }
+        if self.copy_source_if_modified_since.is_some() && self.copy_source_if_unmodified_since.is_some() {
+            return Err(Error::InvalidArgument(
+                "copy_source_if_modified_since and copy_source_if_unmodified_since are mutually exclusive".to_string(),
+            ));
+        }
+        if self.metadata_directive != Some(MetadataDirective::Replace) {
+            let has_content_headers = self.cache_control.is_some()
+                || self.content_disposition.is_some()
+                || self.content_encoding.is_some()
+                || self.content_language.is_some()
+                || self.content_type.is_some()
+                || self.expires.is_some();
+            if !self.user_metadata.is_empty() || has_content_headers {
+                return Err(Error::InvalidArgument(
+                    "user_metadata and content headers are ignored by OSS unless metadata_directive is \
+                     MetadataDirective::Replace"
+                        .to_string(),
+                ));
+            }
+        }
+
         let mut headers = HeaderMap::new();
 
         // Set copy source (required)
@@ -212,8 +355,18 @@ impl CopyObjectOptions {
         }
 
         // Set metadata directive
-        if let Some(metadata_directive) = &self.metadata_directive {
-            headers.insert(HeaderName::from_static("x-oss-metadata-directive"), metadata_directive.parse()?);
+        if let Some(metadata_directive) = self.metadata_directive {
+            headers.insert(
+                HeaderName::from_static("x-oss-metadata-directive"),
+                metadata_directive.as_ref().parse()?,
+            );
+        }
+
+        // Set user-defined metadata
+        for (key, value) in &self.user_metadata {
+            let key = key.to_kebab_case().to_lowercase();
+            let header_name = format!("x-oss-meta-{key}");
+            headers.insert(HeaderName::from_bytes(header_name.as_bytes())?, value.parse()?);
         }
 
         // Set content headers
@@ -266,22 +419,63 @@ impl CopyObjectOptions {
             headers.insert(HeaderName::from_static("x-oss-tagging"), tagging.parse()?);
         }
 
-        if let Some(tagging_directive) = &self.tagging_directive {
-            headers.insert(HeaderName::from_static("x-oss-tagging-directive"), tagging_directive.parse()?);
+        if let Some(tagging_directive) = self.tagging_directive {
+            headers.insert(
+                HeaderName::from_static("x-oss-tagging-directive"),
+                tagging_directive.as_ref().parse()?,
+            );
         }
+
+        // Set SSE-C (customer-provided key) headers
+        if let Some(sse_customer_key) = &self.sse_customer_key {
+            sse_customer_key.insert_headers(&mut headers)?;
+        }
+
+        if let Some(copy_source_sse_customer_key) = &self.copy_source_sse_customer_key {
+            copy_source_sse_customer_key.insert_copy_source_headers(&mut headers)?;
+        }
+
         Ok(headers)
     }
 }
 
-/// CopyObject response
+fn deserialize_optional_crc64<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    s.map(|s| s.parse().map_err(serde::de::Error::custom)).transpose()
+}
+
+/// The XML body of a CopyObject response
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct CopyObjectResult {
+struct CopyObjectResultBody {
     /// The ETag value of the destination object
     #[serde(rename = "ETag")]
+    etag: String,
+    /// The time when the destination object was last modified
+    last_modified: String,
+}
+
+/// The subset of CopyObject's response headers needed to verify integrity; kept separate
+/// from the user-facing result since the ETag/last-modified fields come back in the body.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct CopyObjectResultHeaders {
+    #[serde(rename = "x-oss-hash-crc64ecma", deserialize_with = "deserialize_optional_crc64", default)]
+    hash_crc64ecma: Option<u64>,
+}
+
+/// CopyObject response
+#[derive(Debug, Clone)]
+pub struct CopyObjectResult {
+    /// The ETag value of the destination object
     pub etag: String,
     /// The time when the destination object was last modified
     pub last_modified: String,
+    /// The CRC64-ECMA checksum of the destination object, as reported by
+    /// `x-oss-hash-crc64ecma`.
+    pub hash_crc64ecma: Option<u64>,
 }
 
 /// CopyObject operation
@@ -294,7 +488,7 @@ pub struct CopyObject {
 }
 
 impl Ops for CopyObject {
-    type Response = BodyResponseProcessor<CopyObjectResult>;
+    type Response = BodyHeaderResponseProcessor<CopyObjectResultBody, CopyObjectResultHeaders>;
     type Body = ZeroBody;
     type Query = ();
 
@@ -317,6 +511,10 @@ impl Ops for CopyObject {
 pub trait CopyObjectOperations {
     /// Copy an object within a bucket or between buckets in the same region
     ///
+    /// To copy between customer-encrypted (SSE-C) objects, set
+    /// [`CopyObjectOptions::sse_customer_key`] for the destination and
+    /// [`CopyObjectOptions::copy_source_sse_customer_key`] for the source.
+    ///
     /// Official documentation: <https://www.alibabacloud.com/help/en/oss/developer-reference/copyobject>
     fn copy_object(
         &self,
@@ -339,6 +537,20 @@ pub trait CopyObjectOperations {
     ) -> impl Future<Output = Result<CopyObjectResult>>;
 }
 
+/// Compare an (optional) expected CRC64 against the one the server reported, erroring on
+/// a mismatch. Either side being absent is not itself an error.
+fn verify_crc64(expected: Option<u64>, computed: Option<u64>) -> Result<()> {
+    if let (Some(expected), Some(computed)) = (expected, computed) {
+        if expected != computed {
+            return Err(Error::Crc64Mismatch {
+                expected: expected.to_string(),
+                computed: computed.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
 impl CopyObjectOperations for Client {
     async fn copy_object(
         &self,
@@ -350,16 +562,24 @@ impl CopyObjectOperations for Client {
         let source_bucket = source_bucket.into();
         let source_key = source_key.into();
         let target_key = target_key.into();
+        let options = options.unwrap_or_default();
+        let expected_crc64 = options.expected_crc64;
 
         let ops = CopyObject {
             source_bucket,
             source_key,
             source_version_id: None,
             target_key,
-            options: options.unwrap_or_default(),
+            options,
         };
 
-        self.request(ops).await
+        let (body, headers) = self.request(ops).await?;
+        verify_crc64(expected_crc64, headers.hash_crc64ecma)?;
+        Ok(CopyObjectResult {
+            etag: body.etag,
+            last_modified: body.last_modified,
+            hash_crc64ecma: headers.hash_crc64ecma,
+        })
     }
 
     async fn copy_object_with_version_id(
@@ -375,6 +595,7 @@ impl CopyObjectOperations for Client {
         let source_version_id = source_version_id.into();
         let target_key = target_key.into();
         let options = options.unwrap_or_default();
+        let expected_crc64 = options.expected_crc64;
 
         let ops = CopyObject {
             source_bucket,
@@ -384,6 +605,12 @@ impl CopyObjectOperations for Client {
             options,
         };
 
-        self.request(ops).await
+        let (body, headers) = self.request(ops).await?;
+        verify_crc64(expected_crc64, headers.hash_crc64ecma)?;
+        Ok(CopyObjectResult {
+            etag: body.etag,
+            last_modified: body.last_modified,
+            hash_crc64ecma: headers.hash_crc64ecma,
+        })
     }
 }
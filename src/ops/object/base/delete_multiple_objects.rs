@@ -0,0 +1,178 @@
+use std::future::Future;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use http::{HeaderMap, HeaderName, HeaderValue, Method};
+use serde::{Deserialize, Serialize};
+
+use crate::body::MakeBody;
+use crate::error::Result;
+use crate::response::BodyResponseProcessor;
+use crate::ser::OnlyKeyField;
+use crate::{Client, Ops, Prepared, Request};
+
+/// A single object (and optional version) targeted by a `DeleteMultipleObjects` call.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ObjectToDelete {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+}
+
+impl ObjectToDelete {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            version_id: None,
+        }
+    }
+
+    pub fn version_id(mut self, version_id: impl Into<String>) -> Self {
+        self.version_id = Some(version_id.into());
+        self
+    }
+}
+
+/// DeleteMultipleObjects request parameters
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct DeleteMultipleObjectsParams {
+    delete: OnlyKeyField,
+}
+
+impl DeleteMultipleObjectsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// DeleteMultipleObjects request body
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename = "Delete")]
+pub struct DeleteMultipleObjectsBody {
+    #[serde(rename = "Quiet")]
+    pub quiet: bool,
+    #[serde(rename = "Object", default)]
+    pub objects: Vec<ObjectToDelete>,
+}
+
+impl DeleteMultipleObjectsBody {
+    pub fn new(objects: Vec<ObjectToDelete>) -> Self {
+        Self { quiet: false, objects }
+    }
+
+    /// Suppress per-key success entries in the response, keeping only `errors`.
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+}
+
+/// A successfully deleted object, as reported by `DeleteMultipleObjects`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeletedObject {
+    pub key: String,
+    #[serde(rename = "VersionId")]
+    pub version_id: Option<String>,
+    /// Whether this delete created a delete marker (versioned bucket).
+    #[serde(default)]
+    pub delete_marker: bool,
+    #[serde(rename = "DeleteMarkerVersionId")]
+    pub delete_marker_version_id: Option<String>,
+}
+
+/// A per-key failure, as reported by `DeleteMultipleObjects`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeleteError {
+    pub key: String,
+    #[serde(rename = "VersionId")]
+    pub version_id: Option<String>,
+    pub code: String,
+    pub message: String,
+}
+
+/// DeleteMultipleObjects response
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeleteResult {
+    /// Keys successfully deleted. Empty when `DeleteMultipleObjectsBody::quiet` was set.
+    #[serde(rename = "Deleted", default)]
+    pub deleted: Vec<DeletedObject>,
+    /// Keys that failed to delete.
+    #[serde(rename = "Error", default)]
+    pub errors: Vec<DeleteError>,
+}
+
+/// Body type for [`DeleteMultipleObjects`]: unlike [`XMLBody`](crate::body::XMLBody), it
+/// carries an already-serialized XML string instead of serializing its `T` itself, since
+/// `DeleteMultipleObjects::prepare` needs the serialized bytes up front anyway to compute
+/// the required `Content-MD5` header and serializing twice would be wasted work on a
+/// batch that can carry up to 1000 keys.
+pub struct PreEncodedXmlBody;
+
+impl MakeBody for PreEncodedXmlBody {
+    type Body = String;
+
+    fn make_body(body: Self::Body, request: &mut reqwest::Request) -> Result<()> {
+        let headers = request.headers_mut();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_str(&body.len().to_string())?);
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/xml"));
+        request.body_mut().replace(reqwest::Body::from(body));
+        Ok(())
+    }
+}
+
+/// DeleteMultipleObjects operation
+pub struct DeleteMultipleObjects {
+    pub params: DeleteMultipleObjectsParams,
+    pub body: DeleteMultipleObjectsBody,
+}
+
+impl Ops for DeleteMultipleObjects {
+    type Response = BodyResponseProcessor<DeleteResult>;
+    type Body = PreEncodedXmlBody;
+    type Query = DeleteMultipleObjectsParams;
+
+    fn prepare(self) -> Result<Prepared<DeleteMultipleObjectsParams>> {
+        // OSS requires Content-MD5 of the exact bytes sent so it can detect corruption of
+        // the object list.
+        let xml = quick_xml::se::to_string(&self.body)?;
+        let content_md5 = BASE64.encode(md5::compute(xml.as_bytes()).0);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(HeaderName::from_static("content-md5"), content_md5.parse()?);
+
+        Ok(Prepared {
+            method: Method::POST,
+            query: Some(self.params),
+            headers: Some(headers),
+            body: Some(xml),
+            ..Default::default()
+        })
+    }
+}
+
+/// Trait for DeleteMultipleObjects operations
+pub trait DeleteMultipleObjectsOperations {
+    /// Delete up to 1000 objects (optionally by specific version) in a single request
+    /// instead of one `DeleteObject` round trip per key.
+    ///
+    /// Official documentation: <https://www.alibabacloud.com/help/en/oss/developer-reference/deletemultipleobjects>
+    fn delete_multiple_objects(
+        &self,
+        body: DeleteMultipleObjectsBody,
+    ) -> impl Future<Output = Result<DeleteResult>>;
+}
+
+impl DeleteMultipleObjectsOperations for Client {
+    async fn delete_multiple_objects(&self, body: DeleteMultipleObjectsBody) -> Result<DeleteResult> {
+        let ops = DeleteMultipleObjects {
+            params: DeleteMultipleObjectsParams::new(),
+            body,
+        };
+        self.request(ops).await
+    }
+}
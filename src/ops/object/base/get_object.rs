@@ -1,13 +1,19 @@
 use std::future::Future;
+use std::pin::Pin;
 
 use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
 use http::{HeaderMap, Method, header};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use super::client_side_encryption::ClientSideDecryptor;
+use super::compression;
+use super::{ClientSideEncryptionKey, SseCustomerKey};
 use crate::body::NoneBody;
-use crate::error::Result;
-use crate::response::BinaryResponseProcessor;
-use crate::{Client, Ops, Prepared, QueryAuthOptions, Request};
+use crate::error::{Error, Result};
+use crate::response::{BinaryHeaderResponseProcessor, StreamHeaderResponseProcessor};
+use crate::utils::Crc64;
+use crate::{BoxError, Client, Ops, Prepared, QueryAuthOptions, Request};
 
 /// GetObject request parameters
 #[derive(Debug, Clone, Default, Serialize)]
@@ -105,6 +111,24 @@ pub struct GetObjectOptions {
     pub if_none_match: Option<String>,
     /// Accepted encoding format
     pub accept_encoding: Option<String>,
+    /// Advertise `Accept-Encoding: gzip, deflate, zstd, br` (unless `accept_encoding` is
+    /// already set) and transparently decompress the body if OSS returns it encoded with
+    /// one of those, mirroring [`PutObjectOptions::compression`](super::PutObjectOptions::compression)
+    /// on the way back down. Has no effect on objects that weren't compressed. Fails with
+    /// [`Error::Other`](crate::Error::Other) if OSS returns a `Content-Encoding` this crate
+    /// doesn't recognize.
+    pub decompress: bool,
+    /// Verify the downloaded bytes against the `x-oss-hash-crc64ecma` response header,
+    /// failing with [`Error::Crc64Mismatch`](crate::Error::Crc64Mismatch) on a mismatch.
+    pub verify_crc64: bool,
+    /// The customer-provided SSE-C key the object was encrypted with, needed to decrypt
+    /// it on download.
+    pub sse_customer_key: Option<SseCustomerKey>,
+    /// The master key the object was wrapped with via
+    /// [`PutObjectOptions::client_side_encryption_key`](super::PutObjectOptions::client_side_encryption_key),
+    /// needed to recover the per-object content-encryption key from the object's
+    /// `x-oss-meta-client-side-encryption-*` metadata and decrypt it on download.
+    pub client_side_encryption_key: Option<ClientSideEncryptionKey>,
 }
 
 impl GetObjectOptions {
@@ -143,6 +167,33 @@ impl GetObjectOptions {
         self.accept_encoding = Some(encoding.into());
         self
     }
+
+    /// Advertise support for gzip/deflate/zstd/br and transparently decompress the body
+    /// if OSS returns it encoded.
+    pub fn decompress(mut self, decompress: bool) -> Self {
+        self.decompress = decompress;
+        self
+    }
+
+    /// Verify the downloaded bytes against the `x-oss-hash-crc64ecma` response header.
+    pub fn verify_crc64(mut self, verify: bool) -> Self {
+        self.verify_crc64 = verify;
+        self
+    }
+
+    /// Decrypt the object with the customer-provided 256-bit AES key (SSE-C) it was
+    /// uploaded with via [`PutObjectOptions::sse_customer_key`](super::PutObjectOptions::sse_customer_key).
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Decrypt the object with the client-side master key it was wrapped with via
+    /// [`PutObjectOptions::client_side_encryption_key`](super::PutObjectOptions::client_side_encryption_key).
+    pub fn client_side_encryption_key(mut self, master_key: [u8; 32]) -> Self {
+        self.client_side_encryption_key = Some(ClientSideEncryptionKey::new(master_key));
+        self
+    }
 }
 
 impl GetObjectOptions {
@@ -172,14 +223,39 @@ impl GetObjectOptions {
         }
 
         // Set Accept-Encoding header
-        if let Some(accept_encoding) = self.accept_encoding {
-            headers.insert(header::ACCEPT_ENCODING, accept_encoding.parse()?);
+        match self.accept_encoding {
+            Some(accept_encoding) => {
+                headers.insert(header::ACCEPT_ENCODING, accept_encoding.parse()?);
+            },
+            None if self.decompress => {
+                headers.insert(header::ACCEPT_ENCODING, "gzip, deflate, zstd, br".parse()?);
+            },
+            None => {},
+        }
+
+        // Set SSE-C (customer-provided key) headers
+        if let Some(sse_customer_key) = &self.sse_customer_key {
+            sse_customer_key.insert_headers(&mut headers)?;
         }
 
         Ok(headers)
     }
 }
 
+/// The subset of GetObject's response headers needed to verify integrity; kept separate
+/// from a user-facing response type since GetObject otherwise only surfaces the body.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct GetObjectHeaders {
+    #[serde(rename = "x-oss-hash-crc64ecma")]
+    hash_crc64ecma: Option<String>,
+    #[serde(rename = "content-encoding")]
+    content_encoding: Option<String>,
+    #[serde(rename = "x-oss-meta-client-side-encryption-key")]
+    client_side_encryption_key: Option<String>,
+    #[serde(rename = "x-oss-meta-client-side-encryption-start")]
+    client_side_encryption_start: Option<String>,
+}
+
 /// GetObject operation
 pub struct GetObject {
     pub object_key: String,
@@ -188,7 +264,50 @@ pub struct GetObject {
 }
 
 impl Ops for GetObject {
-    type Response = BinaryResponseProcessor;
+    type Response = BinaryHeaderResponseProcessor<GetObjectHeaders>;
+    type Body = NoneBody;
+    type Query = GetObjectParams;
+
+    fn prepare(self) -> Result<Prepared<GetObjectParams>> {
+        Ok(Prepared {
+            method: Method::GET,
+            key: Some(self.object_key),
+            query: Some(self.params),
+            headers: Some(self.options.into_headers()?),
+            ..Default::default()
+        })
+    }
+}
+
+/// The subset of GetObject's response headers surfaced alongside a streamed body, so a
+/// caller issuing a [`GetObjectOptions::range`] request can tell how much of the object it
+/// got back without waiting on the stream to drain.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GetObjectStreamHeaders {
+    /// Present when the request was satisfied as a partial download, e.g. `bytes 0-1023/4096`.
+    #[serde(rename = "content-range")]
+    pub content_range: Option<String>,
+    /// The length in bytes of the (possibly partial) body that follows.
+    #[serde(rename = "content-length")]
+    pub content_length: Option<String>,
+    #[serde(rename = "content-encoding")]
+    content_encoding: Option<String>,
+    #[serde(rename = "x-oss-meta-client-side-encryption-key")]
+    client_side_encryption_key: Option<String>,
+    #[serde(rename = "x-oss-meta-client-side-encryption-start")]
+    client_side_encryption_start: Option<String>,
+}
+
+/// GetObject operation that streams the response body instead of buffering it, used by
+/// [`GetObjectOperations::get_object_stream`].
+pub struct GetObjectStream {
+    pub object_key: String,
+    pub params: GetObjectParams,
+    pub options: GetObjectOptions,
+}
+
+impl Ops for GetObjectStream {
+    type Response = StreamHeaderResponseProcessor<GetObjectStreamHeaders>;
     type Body = NoneBody;
     type Query = GetObjectParams;
 
@@ -215,6 +334,22 @@ pub trait GetObjectOperations {
         options: Option<GetObjectOptions>,
     ) -> impl Future<Output = Result<Bytes>>;
 
+    /// Get an object (file) as a stream of chunks instead of buffering the whole body,
+    /// so a multi-gigabyte object (or a single [`range`](GetObjectOptions::range) of one)
+    /// can be piped straight to disk without holding it all in memory at once. The returned
+    /// [`GetObjectStreamHeaders`] report how much of the object the stream will yield.
+    fn get_object_stream(
+        &self,
+        object_key: impl Into<String>,
+        params: GetObjectParams,
+        options: Option<GetObjectOptions>,
+    ) -> impl Future<
+        Output = Result<(
+            Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>,
+            GetObjectStreamHeaders,
+        )>,
+    >;
+
     fn presign_get_object(
         &self,
         object_key: impl Into<String>,
@@ -232,13 +367,115 @@ impl GetObjectOperations for Client {
         params: GetObjectParams,
         options: Option<GetObjectOptions>,
     ) -> Result<Bytes> {
+        let options = options.unwrap_or_default();
+        // OSS reports `x-oss-hash-crc64ecma` as the CRC of the *whole* object, which only
+        // matches the downloaded bytes when the whole object was requested.
+        let verify_crc64 = options.verify_crc64 && options.range.is_none();
+        let decompress = options.decompress;
+        let client_side_encryption_key = options.client_side_encryption_key.clone();
+        // The STREAM/AEAD envelope authenticates and chunks the whole ciphertext with a
+        // nonce counter starting at chunk 0, so decrypting an arbitrary byte range would
+        // either fail AEAD verification or silently decrypt the wrong keystream region.
+        if client_side_encryption_key.is_some() && options.range.is_some() {
+            return Err(Error::InvalidArgument(
+                "client_side_encryption_key cannot be used with a range read".to_string(),
+            ));
+        }
+
         let ops = GetObject {
             object_key: object_key.into(),
             params,
-            options: options.unwrap_or_default(),
+            options,
+        };
+
+        let (bytes, headers) = self.request(ops).await?;
+
+        // CRC64 covers the bytes OSS actually stored (i.e. before any decryption or
+        // decompression), so verify it first.
+        if verify_crc64 {
+            if let Some(expected) = &headers.hash_crc64ecma {
+                let mut hasher = Crc64::new();
+                hasher.update(&bytes);
+                let computed = hasher.digest();
+                if *expected != computed {
+                    return Err(Error::Crc64Mismatch {
+                        expected: expected.clone(),
+                        computed,
+                    });
+                }
+            }
+        }
+
+        let bytes = match (&client_side_encryption_key, &headers.client_side_encryption_key, &headers.client_side_encryption_start) {
+            (Some(master_key), Some(wrapped_cek), Some(nonce_prefix)) => {
+                let decryptor = ClientSideDecryptor::from_metadata(master_key, wrapped_cek, nonce_prefix)?;
+                decryptor.decode_bytes(&bytes)?
+            },
+            _ => bytes,
+        };
+
+        if decompress {
+            if let Some(content_encoding) = &headers.content_encoding {
+                if let Some(decoded) = compression::decode_bytes(content_encoding, &bytes)? {
+                    return Ok(decoded);
+                }
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    async fn get_object_stream(
+        &self,
+        object_key: impl Into<String>,
+        params: GetObjectParams,
+        options: Option<GetObjectOptions>,
+    ) -> Result<(Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>, GetObjectStreamHeaders)> {
+        let options = options.unwrap_or_default();
+        let decompress = options.decompress;
+        let client_side_encryption_key = options.client_side_encryption_key.clone();
+        // See the matching check in `get_object`: the STREAM/AEAD envelope can't decrypt an
+        // arbitrary byte range.
+        if client_side_encryption_key.is_some() && options.range.is_some() {
+            return Err(Error::InvalidArgument(
+                "client_side_encryption_key cannot be used with a range read".to_string(),
+            ));
+        }
+
+        let ops = GetObjectStream {
+            object_key: object_key.into(),
+            params,
+            options,
+        };
+        let (stream, headers) = self.request(ops).await?;
+
+        // Reverses `put_object_with_options`'s compress-then-encrypt order: decrypt the
+        // wire bytes first, then decompress what they decrypt to.
+        let stream: Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>> = match (
+            &client_side_encryption_key,
+            &headers.client_side_encryption_key,
+            &headers.client_side_encryption_start,
+        ) {
+            (Some(master_key), Some(wrapped_cek), Some(nonce_prefix)) => {
+                let decryptor = ClientSideDecryptor::from_metadata(master_key, wrapped_cek, nonce_prefix)?;
+                let boxed: crate::body::BoxedByteStream = Box::pin(stream.map_err(|err| Box::new(err) as BoxError));
+                Box::pin(decryptor.into_stream(boxed).map_err(|err| Error::Other(err.to_string())))
+            },
+            _ => stream,
+        };
+
+        let stream = match (decompress, &headers.content_encoding) {
+            (true, Some(content_encoding)) => {
+                let boxed: crate::body::BoxedByteStream =
+                    Box::pin(stream.map_err(|err| Box::new(err) as BoxError));
+                let decompressed = compression::decompress_stream(content_encoding, boxed)?;
+                Box::pin(decompressed.map_err(|err| Error::Other(err.to_string())))
+                    as Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>
+            },
+            _ => stream,
         };
 
-        self.request(ops).await
+        Ok((stream, headers))
     }
 
     async fn presign_get_object(
@@ -254,7 +491,7 @@ impl GetObjectOperations for Client {
             params,
             options: options.unwrap_or_default(),
         };
-        self.presign(ops, public, Some(query_auth_options)).await
+        self.build_presigned_url(ops, public, Some(query_auth_options)).await
     }
 }
 
@@ -296,6 +533,13 @@ impl GetObjectRequestBuilder {
         self
     }
 
+    /// Set Range header to the last `n` bytes of the object (e.g. `bytes=-128`), for
+    /// reading a trailer (footer/central directory) without downloading the whole object.
+    pub fn range_suffix(mut self, n: u64) -> Self {
+        self.options.range = Some(format!("bytes=-{n}"));
+        self
+    }
+
     /// Set the If-Modified-Since header
     pub fn if_modified_since(mut self, time: impl Into<String>) -> Self {
         self.options.if_modified_since = Some(time.into());
@@ -320,6 +564,33 @@ impl GetObjectRequestBuilder {
         self
     }
 
+    /// Verify the downloaded bytes against the `x-oss-hash-crc64ecma` response header
+    pub fn verify_crc64(mut self, verify: bool) -> Self {
+        self.options.verify_crc64 = verify;
+        self
+    }
+
+    /// Advertise support for gzip/deflate/zstd/br and transparently decompress the body
+    /// if OSS returns it encoded.
+    pub fn decompress(mut self, decompress: bool) -> Self {
+        self.options.decompress = decompress;
+        self
+    }
+
+    /// Decrypt the object with the customer-provided 256-bit AES key (SSE-C) it was
+    /// uploaded with
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.options.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Decrypt the object with the client-side master key it was wrapped with via
+    /// [`PutObjectOptions::client_side_encryption_key`](super::PutObjectOptions::client_side_encryption_key).
+    pub fn client_side_encryption_key(mut self, master_key: [u8; 32]) -> Self {
+        self.options.client_side_encryption_key = Some(ClientSideEncryptionKey::new(master_key));
+        self
+    }
+
     /// Set the response Cache-Control header
     pub fn response_cache_control(mut self, cache_control: impl Into<String>) -> Self {
         self.params.response_cache_control = Some(cache_control.into());
@@ -346,6 +617,10 @@ impl GetObjectRequestBuilder {
             || self.options.if_match.is_some()
             || self.options.if_none_match.is_some()
             || self.options.accept_encoding.is_some()
+            || self.options.decompress
+            || self.options.verify_crc64
+            || self.options.sse_customer_key.is_some()
+            || self.options.client_side_encryption_key.is_some()
         {
             Some(self.options)
         } else {
@@ -6,7 +6,7 @@ use chrono::{DateTime, FixedOffset};
 use http::{HeaderMap, Method, header};
 use serde::{Deserialize, Deserializer, Serialize};
 
-use super::StorageClass;
+use super::{SseCustomerKey, StorageClass};
 use crate::body::EmptyBody;
 use crate::error::Result;
 use crate::response::HeaderResponseProcessor;
@@ -154,10 +154,13 @@ pub struct HeadObjectOptions {
     pub if_unmodified_since: Option<String>,
     pub if_match: Option<String>,
     pub if_none_match: Option<String>,
+    /// The customer-provided SSE-C key the object was encrypted with, needed to read its
+    /// metadata back.
+    pub sse_customer_key: Option<SseCustomerKey>,
 }
 
 /// HeadObjectRequest builder
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct HeadObjectRequestBuilder {
     /// Return 200 OK and Object Meta if the time in the parameter is earlier than the actual modification time; otherwise return 304 Not Modified
     pub if_modified_since: Option<String>,
@@ -167,16 +170,13 @@ pub struct HeadObjectRequestBuilder {
     pub if_match: Option<String>,
     /// Return 200 OK and Object Meta if the expected ETag value does not match the Object's ETag; otherwise return 304 Not Modified
     pub if_none_match: Option<String>,
+    /// The customer-provided SSE-C key the object was encrypted with
+    pub sse_customer_key: Option<SseCustomerKey>,
 }
 
 impl HeadObjectRequestBuilder {
     pub fn new() -> Self {
-        Self {
-            if_modified_since: None,
-            if_unmodified_since: None,
-            if_match: None,
-            if_none_match: None,
-        }
+        Self::default()
     }
 
     /// Set the If-Modified-Since header
@@ -203,22 +203,24 @@ impl HeadObjectRequestBuilder {
         self
     }
 
+    /// Decrypt the object with the customer-provided 256-bit AES key (SSE-C) it was
+    /// uploaded with
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
     pub fn build(self) -> HeadObjectOptions {
         HeadObjectOptions {
             if_modified_since: self.if_modified_since,
             if_unmodified_since: self.if_unmodified_since,
             if_match: self.if_match,
             if_none_match: self.if_none_match,
+            sse_customer_key: self.sse_customer_key,
         }
     }
 }
 
-impl Default for HeadObjectRequestBuilder {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 /// HeadObject operation
 pub struct HeadObject {
     pub object_name: String,
@@ -265,6 +267,10 @@ impl Ops for HeadObject {
             headers.insert(header::IF_NONE_MATCH, if_none_match.parse()?);
         }
 
+        if let Some(sse_customer_key) = &options.sse_customer_key {
+            sse_customer_key.insert_headers(&mut headers)?;
+        }
+
         Ok(Some(headers))
     }
 }
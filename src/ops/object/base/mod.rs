@@ -1,11 +1,20 @@
+mod client_side_encryption;
+mod compression;
 mod copy_object;
+mod delete_multiple_objects;
 mod delete_object;
 mod get_object;
+mod get_object_parallel;
 mod head_object;
 mod put_object;
 
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use http::{HeaderMap, HeaderName};
 use serde::{Deserialize, Serialize};
 
+use crate::error::Result;
+
 /// OSS storage class
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
 #[serde(rename_all_fields = "lowercase")]
@@ -59,8 +68,66 @@ impl AsRef<str> for ServerSideEncryption {
     }
 }
 
+/// A customer-provided SSE-C encryption key: a raw 256-bit AES key that never leaves the
+/// client, attached to a put/get/head/copy operation to have OSS encrypt or decrypt that
+/// request's object with it instead of a server-managed key.
+///
+/// Wrapped so that a containing options struct's derived `Debug` can't accidentally print
+/// the raw key bytes, and so the key material is wiped from memory once it's no longer
+/// needed. The key length is validated for free by taking `[u8; 32]` rather than a `Vec<u8>`
+/// or slice, so there's no separate length check to get wrong.
+#[derive(Clone)]
+pub struct SseCustomerKey([u8; 32]);
+
+impl SseCustomerKey {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// Insert the `x-oss-server-side-encryption-customer-*` headers OSS needs to
+    /// encrypt/decrypt the request's own object with this key.
+    pub(crate) fn insert_headers(&self, headers: &mut HeaderMap) -> Result<()> {
+        self.insert_headers_with_prefix(headers, "x-oss-server-side-encryption-customer")
+    }
+
+    /// Insert the `x-oss-copy-source-server-side-encryption-customer-*` headers OSS needs
+    /// to decrypt a copy *source* that was written with this key.
+    pub(crate) fn insert_copy_source_headers(&self, headers: &mut HeaderMap) -> Result<()> {
+        self.insert_headers_with_prefix(headers, "x-oss-copy-source-server-side-encryption-customer")
+    }
+
+    fn insert_headers_with_prefix(&self, headers: &mut HeaderMap, prefix: &str) -> Result<()> {
+        let key_b64 = BASE64.encode(self.0);
+        let key_md5_b64 = BASE64.encode(md5::compute(self.0).0);
+
+        headers.insert(HeaderName::from_bytes(format!("{prefix}-algorithm").as_bytes())?, "AES256".parse()?);
+        headers.insert(HeaderName::from_bytes(format!("{prefix}-key").as_bytes())?, key_b64.parse()?);
+        headers.insert(HeaderName::from_bytes(format!("{prefix}-key-MD5").as_bytes())?, key_md5_b64.parse()?);
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for SseCustomerKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SseCustomerKey(..)")
+    }
+}
+
+impl Drop for SseCustomerKey {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.0.zeroize();
+    }
+}
+
+pub use client_side_encryption::ClientSideEncryptionKey;
+pub(crate) use client_side_encryption::{ClientSideDecryptor, ClientSideEncryptor, ClientSideEncryptorSealer};
+pub use compression::Compression;
 pub use copy_object::*;
+pub use delete_multiple_objects::*;
 pub use delete_object::*;
 pub use get_object::*;
+pub use get_object_parallel::*;
 pub use head_object::*;
 pub use put_object::*;
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::convert::Infallible;
 use std::future::Future;
+use std::sync::{Arc, Mutex};
 
 use bytes::Bytes;
 use futures::{TryStream, stream};
@@ -8,10 +9,12 @@ use heck::ToKebabCase;
 use http::{HeaderMap, HeaderName, Method, header};
 use serde::{Deserialize, Serialize};
 
-use super::{ServerSideEncryption, StorageClass};
-use crate::body::StreamBody;
-use crate::error::Result;
+use super::client_side_encryption::ClientSideEncryptor;
+use super::{ClientSideEncryptionKey, Compression, ServerSideEncryption, SseCustomerKey, StorageClass};
+use crate::body::{Crc64Stream, StreamBody, box_byte_stream};
+use crate::error::{Error, Result};
 use crate::response::HeaderResponseProcessor;
+use crate::utils::Crc64;
 use crate::{BoxError, Client, Ops, Prepared, Request, ser};
 
 /// PutObject request parameters (query parameters)
@@ -54,6 +57,25 @@ pub struct PutObjectOptions {
     pub server_side_encryption: Option<ServerSideEncryption>,
     /// Server-side encryption key ID (used by KMS)
     pub server_side_encryption_key_id: Option<String>,
+    /// Customer-provided SSE-C encryption key. When set, this takes precedence over
+    /// `server_side_encryption`/`server_side_encryption_key_id` for the headers emitted.
+    pub sse_customer_key: Option<SseCustomerKey>,
+    /// Transparently encrypt the object with a client-side-generated AES-256-GCM key
+    /// before it leaves the process, wrapping that key under this master key. See
+    /// [`ClientSideEncryptionKey`].
+    pub client_side_encryption_key: Option<ClientSideEncryptionKey>,
+    /// Compute the CRC64-ECMA checksum of the body as it streams and verify it against
+    /// the `x-oss-hash-crc64ecma` value OSS returns, failing with
+    /// [`Error::Crc64Mismatch`](crate::Error::Crc64Mismatch) on a mismatch.
+    pub verify_crc64: bool,
+    /// Gzip- or zstd-compress the body before upload and set `Content-Encoding` to match.
+    /// Skipped if `content_encoding` is already set. See [`Compression`].
+    pub compression: Option<Compression>,
+    /// Skip `compression` for bodies smaller than this many bytes. Only takes effect when
+    /// the body's length is known up front (i.e. via [`PutObjectOperations::put_object`]);
+    /// streamed bodies of unknown length always compress. Has no effect if `compression`
+    /// isn't set.
+    pub compression_min_size: Option<u64>,
     /// Object access control list
     pub object_acl: Option<String>,
     /// User-defined metadata
@@ -119,6 +141,45 @@ impl PutObjectOptions {
         self
     }
 
+    /// Encrypt the object with a customer-provided 256-bit AES key (SSE-C), bypassing
+    /// OSS-managed/KMS encryption for this request. Pass the same key to
+    /// [`GetObjectOptions::sse_customer_key`](super::GetObjectOptions::sse_customer_key) to
+    /// read the object back.
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Transparently encrypt the object client-side with AES-256-GCM before upload,
+    /// wrapping the freshly-generated content-encryption key under `master_key`. The
+    /// wrapped key, base nonce and algorithm identifiers are stored as `x-oss-meta-*`
+    /// entries so [`GetObjectOptions::client_side_encryption_key`](super::GetObjectOptions::client_side_encryption_key)
+    /// can reverse it with the same `master_key`.
+    pub fn client_side_encryption_key(mut self, master_key: [u8; 32]) -> Self {
+        self.client_side_encryption_key = Some(ClientSideEncryptionKey::new(master_key));
+        self
+    }
+
+    /// Verify the uploaded body's CRC64-ECMA checksum against the value OSS returns
+    pub fn verify_crc64(mut self, verify: bool) -> Self {
+        self.verify_crc64 = verify;
+        self
+    }
+
+    /// Gzip- or zstd-compress the body on the fly before upload, setting
+    /// `Content-Encoding` to match. Has no effect if `content_encoding` is already set.
+    pub fn compress(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Skip compression for bodies smaller than `min_size` bytes. Only takes effect when
+    /// the body's length is known up front; streamed bodies always compress.
+    pub fn compression_min_size(mut self, min_size: u64) -> Self {
+        self.compression_min_size = Some(min_size);
+        self
+    }
+
     /// Set object ACL
     pub fn object_acl(mut self, acl: impl Into<String>) -> Self {
         self.object_acl = Some(acl.into());
@@ -216,6 +277,11 @@ impl PutObjectOptions {
             headers.insert(HeaderName::from_static("x-oss-server-side-encryption-key-id"), key_id.parse()?);
         }
 
+        // Set SSE-C (customer-provided key) headers
+        if let Some(sse_customer_key) = &self.sse_customer_key {
+            sse_customer_key.insert_headers(&mut headers)?;
+        }
+
         // Set object ACL
         if let Some(acl) = self.object_acl {
             headers.insert(HeaderName::from_static("x-oss-object-acl"), acl.parse()?);
@@ -256,6 +322,13 @@ pub struct PutObjectResponse {
     /// Server-side encryption key ID
     #[serde(rename = "x-oss-server-side-encryption-key-id")]
     pub server_side_encryption_key_id: Option<String>,
+    /// SSE-C algorithm, echoed back when the request used a customer-provided key
+    #[serde(rename = "x-oss-server-side-encryption-customer-algorithm")]
+    pub server_side_encryption_customer_algorithm: Option<String>,
+    /// Base64-encoded MD5 of the SSE-C key, echoed back when the request used a
+    /// customer-provided key
+    #[serde(rename = "x-oss-server-side-encryption-customer-key-md5")]
+    pub server_side_encryption_customer_key_md5: Option<String>,
 }
 
 /// PutObject operation
@@ -329,14 +402,17 @@ impl PutObjectOperations for Client {
         T: Send + 'static,
         Bytes: From<T>,
     {
-        let ops = PutObject {
-            object_key: object_key.into(),
-            params: PutObjectParams::new(),
-            options: options.unwrap_or_default(),
-            stream_body: stream::once(async move { Result::<Bytes, Infallible>::Ok(body.into()) }),
-        };
-
-        self.request(ops).await
+        let bytes = Bytes::from(body);
+        let known_len = Some(bytes.len() as u64);
+        let stream = stream::once(async move { Result::<Bytes, Infallible>::Ok(bytes) });
+        put_object_with_options(
+            self,
+            object_key.into(),
+            stream,
+            options.unwrap_or_default(),
+            known_len,
+        )
+        .await
     }
 
     async fn put_object_stream<S>(
@@ -350,15 +426,97 @@ impl PutObjectOperations for Client {
         S::Error: Into<BoxError>,
         Bytes: From<S::Ok>,
     {
+        put_object_with_options(
+            self,
+            object_key.into(),
+            stream,
+            options.unwrap_or_default(),
+            None,
+        )
+        .await
+    }
+}
+
+/// Shared by both [`PutObjectOperations::put_object`] and
+/// [`PutObjectOperations::put_object_stream`]: applies whichever of `options`'s body
+/// adapters (compression, client-side encryption, CRC64 verification) are enabled, in
+/// that order, so compression covers the original plaintext, encryption covers the
+/// (possibly compressed) plaintext, and CRC64 verification covers the bytes actually sent
+/// over the wire.
+///
+/// `known_len` is the body length in bytes if known up front (i.e. `put_object`, whose
+/// caller already holds the whole body in memory), used to honor
+/// [`PutObjectOptions::compression_min_size`]. It's `None` for `put_object_stream`, whose
+/// length isn't known until the stream is drained, so that path always compresses.
+async fn put_object_with_options<S>(
+    client: &Client,
+    object_key: String,
+    stream: S,
+    mut options: PutObjectOptions,
+    known_len: Option<u64>,
+) -> Result<PutObjectResponse>
+where
+    S: TryStream + Send + 'static,
+    S::Error: Into<BoxError>,
+    Bytes: From<S::Ok>,
+{
+    let below_min_size = match (options.compression_min_size, known_len) {
+        (Some(min_size), Some(len)) => len < min_size,
+        _ => false,
+    };
+    let compress_enabled =
+        options.compression.is_some() && options.content_encoding.is_none() && !below_min_size;
+    let compression = compress_enabled.then(|| options.compression.take()).flatten();
+    let master_key = options.client_side_encryption_key.take();
+    let verify_crc64 = options.verify_crc64;
+
+    if compression.is_none() && master_key.is_none() && !verify_crc64 {
         let ops = PutObject {
-            object_key: object_key.into(),
+            object_key,
             params: PutObjectParams::new(),
-            options: options.unwrap_or_default(),
+            options,
             stream_body: stream,
         };
+        return client.request(ops).await;
+    }
+
+    let mut stream_body = box_byte_stream(stream);
+
+    if let Some(compression) = compression {
+        options.content_encoding = Some(compression.content_encoding().to_string());
+        stream_body = Box::pin(compression.into_stream(stream_body)?);
+    }
+
+    if let Some(master_key) = master_key {
+        let encryptor = ClientSideEncryptor::generate(&master_key)?;
+        options.user_meta.extend(encryptor.metadata());
+        stream_body = Box::pin(encryptor.into_stream(stream_body));
+    }
 
-        self.request(ops).await
+    let crc_hasher = verify_crc64.then(|| Arc::new(Mutex::new(Crc64::new())));
+    if let Some(hasher) = &crc_hasher {
+        stream_body = Box::pin(Crc64Stream::new(stream_body, hasher.clone()));
     }
+
+    let ops = PutObject {
+        object_key,
+        params: PutObjectParams::new(),
+        options,
+        stream_body,
+    };
+
+    let response = client.request(ops).await?;
+
+    if let Some(hasher) = crc_hasher {
+        let computed = hasher.lock().expect("CRC64 hasher mutex poisoned").digest();
+        if let Some(expected) = &response.hash_crc64ecma {
+            if *expected != computed {
+                return Err(Error::Crc64Mismatch { expected: expected.clone(), computed });
+            }
+        }
+    }
+
+    Ok(response)
 }
 
 // =============================================================================
@@ -430,6 +588,39 @@ impl PutObjectRequestBuilder {
         self
     }
 
+    /// Encrypt the object with a customer-provided 256-bit AES key (SSE-C)
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.options.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Transparently encrypt the object client-side with AES-256-GCM before upload,
+    /// wrapping the content-encryption key under `master_key`
+    pub fn client_side_encryption_key(mut self, master_key: [u8; 32]) -> Self {
+        self.options.client_side_encryption_key = Some(ClientSideEncryptionKey::new(master_key));
+        self
+    }
+
+    /// Verify the uploaded body's CRC64-ECMA checksum against the value OSS returns
+    pub fn verify_crc64(mut self, verify: bool) -> Self {
+        self.options.verify_crc64 = verify;
+        self
+    }
+
+    /// Gzip- or zstd-compress the body on the fly before upload, setting
+    /// `Content-Encoding` to match. Has no effect if `content_encoding` is already set.
+    pub fn compress(mut self, compression: Compression) -> Self {
+        self.options.compression = Some(compression);
+        self
+    }
+
+    /// Skip compression for bodies smaller than `min_size` bytes. Only takes effect when
+    /// the body's length is known up front; streamed bodies always compress.
+    pub fn compression_min_size(mut self, min_size: u64) -> Self {
+        self.options.compression_min_size = Some(min_size);
+        self
+    }
+
     /// Set object ACL
     pub fn object_acl(mut self, acl: impl Into<String>) -> Self {
         self.options.object_acl = Some(acl.into());
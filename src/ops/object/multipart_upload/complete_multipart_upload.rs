@@ -2,12 +2,13 @@ use std::borrow::Cow;
 use std::future::Future;
 
 use http::{HeaderMap, HeaderName, Method};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_with::skip_serializing_none;
 
+use super::{ListPartsOperations, ListPartsParams};
 use crate::body::XMLBody;
-use crate::error::Result;
-use crate::response::BodyResponseProcessor;
+use crate::error::{Error, Result};
+use crate::response::BodyHeaderResponseProcessor;
 use crate::{Client, Ops, Request};
 
 /// CompleteMultipartUpload request parameters
@@ -42,6 +43,39 @@ pub struct CompleteMultipartUploadOptions {
     pub complete_all: Option<bool>,
     /// Object access permissions
     pub object_acl: Option<String>,
+    /// Allow completing with a non-contiguous part number sequence (e.g. `1, 4, 5, 6`).
+    ///
+    /// OSS silently renumbers such a list on completion (to `1, 2, 3, 4` in the example
+    /// above), so by default `CompleteMultipartUploadBody::validate` rejects it locally.
+    /// When set to `true`, the gap is allowed and the resulting original-to-final part
+    /// number mapping is recorded in `CompleteMultipartUploadResult::renumbered_parts`.
+    pub allow_sparse_parts: bool,
+    /// Server-side encryption used to finalize the object.
+    pub encryption: Encryption,
+}
+
+/// Server-side encryption configuration for a completed multipart object.
+#[derive(Debug, Clone, Default)]
+pub enum Encryption {
+    /// Use the bucket's default encryption configuration.
+    #[default]
+    None,
+    /// Encrypt with OSS-managed keys (SSE-OSS).
+    Aes256,
+    /// Encrypt with a KMS-managed key (SSE-KMS).
+    Kms {
+        /// The CMK ID to use for encryption. Uses the bucket's default KMS key if `None`.
+        key_id: Option<String>,
+    },
+    /// Encrypt with a customer-provided key (SSE-C).
+    CustomerProvided {
+        /// The encryption algorithm, e.g. `AES256`.
+        algorithm: String,
+        /// The base64-encoded encryption key.
+        key: String,
+        /// The base64-encoded MD5 digest of the encryption key.
+        key_md5: String,
+    },
 }
 
 /// Part information
@@ -89,6 +123,59 @@ impl CompleteMultipartUploadBody {
         // Ensure sorting by part number
         self.parts.sort_by_key(|p| p.part_number);
     }
+
+    /// Validate the part list against OSS completion rules, returning a typed error
+    /// instead of letting a malformed list reach the server as a remote 400.
+    ///
+    /// Rejects duplicate `part_number`s and values outside the `1..=10000` range. A
+    /// non-contiguous sequence (e.g. `1, 4, 5, 6`) is rejected unless `allow_sparse_parts`
+    /// is `true`, since OSS silently renumbers such a list on completion.
+    pub fn validate(&self, allow_sparse_parts: bool) -> Result<()> {
+        let mut seen = std::collections::HashSet::with_capacity(self.parts.len());
+        let mut prev_part_number = None;
+        let mut contiguous = true;
+
+        for part in &self.parts {
+            if part.part_number == 0 || part.part_number > 10_000 {
+                return Err(Error::InvalidArgument(format!(
+                    "part_number {} is out of range (must be between 1 and 10000)",
+                    part.part_number
+                )));
+            }
+
+            if !seen.insert(part.part_number) {
+                return Err(Error::InvalidArgument(format!("duplicate part_number {}", part.part_number)));
+            }
+
+            if let Some(prev_part_number) = prev_part_number {
+                if part.part_number != prev_part_number + 1 {
+                    contiguous = false;
+                }
+            }
+            prev_part_number = Some(part.part_number);
+        }
+
+        if !contiguous {
+            if !allow_sparse_parts {
+                return Err(Error::InvalidArgument(
+                    "part numbers are non-contiguous; OSS renumbers them on completion (set \
+                     CompleteMultipartUploadOptions::allow_sparse_parts to allow this)"
+                        .to_string(),
+                ));
+            }
+            tracing::warn!("completing multipart upload with non-contiguous part numbers; OSS will renumber them");
+        }
+
+        Ok(())
+    }
+}
+
+/// Maps an originally-assigned part number to the index OSS assigns it after completion
+/// renumbers a sparse part list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartIndexMapping {
+    pub original_part_number: u32,
+    pub final_part_number: u32,
 }
 
 /// CompleteMultipartUpload response
@@ -107,6 +194,37 @@ pub struct CompleteMultipartUploadResult {
     /// Encoding type
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding_type: Option<String>,
+    /// Original-to-final part number mapping, populated when `allow_sparse_parts` allowed
+    /// a non-contiguous part list through local validation.
+    #[serde(skip)]
+    pub renumbered_parts: Option<Vec<PartIndexMapping>>,
+    /// Server-side encryption method used for the completed object, echoed from the
+    /// request's `CompleteMultipartUploadOptions::encryption`.
+    #[serde(skip)]
+    pub server_side_encryption: Option<String>,
+    /// Server-side encryption key ID, echoed from the request's encryption options.
+    #[serde(skip)]
+    pub server_side_encryption_key_id: Option<String>,
+    /// CRC64-ECMA checksum of the whole completed object, from the `x-oss-hash-crc64ecma`
+    /// response header. Compare this against a client-side running combine of each part's
+    /// own `UploadPartResult::hash_crc64ecma` (see [`crate::utils::crc64_combine`]) to verify
+    /// the object assembled server-side without re-downloading it.
+    #[serde(skip)]
+    pub hash_crc64ecma: Option<u64>,
+}
+
+fn deserialize_optional_crc64<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    s.map(|s| s.parse().map_err(serde::de::Error::custom)).transpose()
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct CompleteMultipartUploadResultHeaders {
+    #[serde(rename = "x-oss-hash-crc64ecma", deserialize_with = "deserialize_optional_crc64", default)]
+    hash_crc64ecma: Option<u64>,
 }
 
 /// CompleteMultipartUpload operation
@@ -118,7 +236,7 @@ pub struct CompleteMultipartUpload {
 }
 
 impl Ops for CompleteMultipartUpload {
-    type Response = BodyResponseProcessor<CompleteMultipartUploadResult>;
+    type Response = BodyHeaderResponseProcessor<CompleteMultipartUploadResult, CompleteMultipartUploadResultHeaders>;
     type Body = XMLBody<CompleteMultipartUploadBody>;
     type Query = CompleteMultipartUploadParams;
 
@@ -153,6 +271,30 @@ impl Ops for CompleteMultipartUpload {
             headers.insert(HeaderName::from_static("x-oss-complete-all"), complete_all.to_string().parse()?);
         }
 
+        match &options.encryption {
+            Encryption::None => {},
+            Encryption::Aes256 => {
+                headers.insert(HeaderName::from_static("x-oss-server-side-encryption"), "AES256".parse()?);
+            },
+            Encryption::Kms { key_id } => {
+                headers.insert(HeaderName::from_static("x-oss-server-side-encryption"), "KMS".parse()?);
+                if let Some(key_id) = key_id {
+                    headers.insert(HeaderName::from_static("x-oss-server-side-encryption-key-id"), key_id.parse()?);
+                }
+            },
+            Encryption::CustomerProvided { algorithm, key, key_md5 } => {
+                headers.insert(
+                    HeaderName::from_static("x-oss-server-side-encryption-customer-algorithm"),
+                    algorithm.parse()?,
+                );
+                headers.insert(HeaderName::from_static("x-oss-server-side-encryption-customer-key"), key.parse()?);
+                headers.insert(
+                    HeaderName::from_static("x-oss-server-side-encryption-customer-key-MD5"),
+                    key_md5.parse()?,
+                );
+            },
+        }
+
         Ok(Some(headers))
     }
 
@@ -163,6 +305,13 @@ impl Ops for CompleteMultipartUpload {
     fn body(&self) -> Option<&CompleteMultipartUploadBody> {
         self.body.as_ref()
     }
+
+    /// `CompleteMultipartUpload` uses POST, which is non-idempotent in general, but
+    /// completing the same upload ID with the same set of parts is safe to retry: OSS
+    /// either finishes the original attempt or returns the same result again.
+    fn retryable(&self) -> bool {
+        true
+    }
 }
 
 /// Trait for CompleteMultipartUpload operations
@@ -185,6 +334,19 @@ pub trait CompleteMultipartUploadOperations {
         upload_id: impl AsRef<str>,
         options: Option<CompleteMultipartUploadOptions>,
     ) -> impl Future<Output = Result<CompleteMultipartUploadResult>>;
+
+    /// Page through all uploaded parts via `ListParts`, build a sorted completion body
+    /// from them client-side, and complete the upload.
+    ///
+    /// This is an explicit, inspectable alternative to `complete_multipart_upload_auto`'s
+    /// server-side `x-oss-complete-all` path, for callers who need to verify or filter
+    /// parts (e.g. drop a re-uploaded duplicate) before committing.
+    fn complete_multipart_upload_from_listing(
+        &self,
+        object_key: impl AsRef<str>,
+        upload_id: impl AsRef<str>,
+        options: Option<CompleteMultipartUploadOptions>,
+    ) -> impl Future<Output = Result<CompleteMultipartUploadResult>>;
 }
 
 impl CompleteMultipartUploadOperations for Client {
@@ -198,13 +360,42 @@ impl CompleteMultipartUploadOperations for Client {
         let mut sorted_parts = parts;
         sorted_parts.sort_by_key(|p| p.part_number);
 
+        let allow_sparse_parts = options.as_ref().is_some_and(|options| options.allow_sparse_parts);
+        let body = CompleteMultipartUploadBody::new(sorted_parts);
+        body.validate(allow_sparse_parts)?;
+
+        let renumbered_parts = allow_sparse_parts.then(|| {
+            body.parts
+                .iter()
+                .enumerate()
+                .map(|(index, part)| PartIndexMapping {
+                    original_part_number: part.part_number,
+                    final_part_number: index as u32 + 1,
+                })
+                .collect()
+        });
+
+        let encryption_echo = options.as_ref().map(|options| match &options.encryption {
+            Encryption::None => (None, None),
+            Encryption::Aes256 => (Some("AES256".to_string()), None),
+            Encryption::Kms { key_id } => (Some("KMS".to_string()), key_id.clone()),
+            Encryption::CustomerProvided { .. } => (None, None),
+        });
+
         let ops = CompleteMultipartUpload {
             object_key: object_key.as_ref().to_string(),
             params: CompleteMultipartUploadParams::new(upload_id.as_ref()),
-            body: Some(CompleteMultipartUploadBody::new(sorted_parts)),
+            body: Some(body),
             options,
         };
-        self.request(ops).await
+        let (mut result, headers) = self.request(ops).await?;
+        result.renumbered_parts = renumbered_parts;
+        result.hash_crc64ecma = headers.hash_crc64ecma;
+        if let Some((server_side_encryption, server_side_encryption_key_id)) = encryption_echo {
+            result.server_side_encryption = server_side_encryption;
+            result.server_side_encryption_key_id = server_side_encryption_key_id;
+        }
+        Ok(result)
     }
 
     async fn complete_multipart_upload_auto(
@@ -222,7 +413,38 @@ impl CompleteMultipartUploadOperations for Client {
             body: None, // No need to provide body when auto-completing
             options: Some(auto_options),
         };
-        self.request(ops).await
+        let (mut result, headers) = self.request(ops).await?;
+        result.hash_crc64ecma = headers.hash_crc64ecma;
+        Ok(result)
+    }
+
+    async fn complete_multipart_upload_from_listing(
+        &self,
+        object_key: impl AsRef<str>,
+        upload_id: impl AsRef<str>,
+        options: Option<CompleteMultipartUploadOptions>,
+    ) -> Result<CompleteMultipartUploadResult> {
+        let object_key = object_key.as_ref();
+        let upload_id = upload_id.as_ref();
+
+        let mut parts = Vec::new();
+        let mut part_number_marker = None;
+        loop {
+            let mut list_params = ListPartsParams::new(upload_id).max_parts(1000);
+            if let Some(marker) = part_number_marker {
+                list_params = list_params.part_number_marker(marker);
+            }
+
+            let listing = self.list_parts(object_key, upload_id, Some(list_params)).await?;
+            parts.extend(listing.parts.into_iter().map(|part| Part::new(part.part_number, part.etag)));
+
+            if !listing.is_truncated {
+                break;
+            }
+            part_number_marker = Some(listing.next_part_number_marker);
+        }
+
+        self.complete_multipart_upload(object_key, upload_id, parts, options).await
     }
 }
 
@@ -0,0 +1,331 @@
+use std::future::Future;
+
+use futures::StreamExt;
+use futures::stream::FuturesUnordered;
+
+use super::{
+    AbortMultipartUploadOperations, CompleteMultipartUploadOperations, CompleteMultipartUploadOptions,
+    CompleteMultipartUploadResult, InitiateMultipartUploadOperations, InitiateMultipartUploadOptions, Part,
+    UploadPartCopyOperations, UploadPartCopyOptions,
+};
+use super::super::base::{CopyObjectOperations, CopyObjectOptions, CopyObjectResult, HeadObjectOperations, HeadObjectParams};
+use crate::Client;
+use crate::error::{Error, Result};
+use crate::utils::crc64_combine;
+
+/// The minimum size (in bytes) OSS accepts for any part other than the last one.
+const MIN_PART_SIZE: u64 = 100 * 1024;
+
+/// Default part size used by [`copy_object_large`](CopyObjectLargeOperations::copy_object_large).
+const DEFAULT_PART_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Default number of `UploadPartCopy` requests kept in flight at once.
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+/// OSS caps a multipart upload at 10000 parts.
+const MAX_PARTS: u64 = 10000;
+
+/// Default size below which [`copy_object_large`](CopyObjectLargeOperations::copy_object_large)
+/// falls back to a single-shot `CopyObject` instead of a multipart copy. OSS rejects
+/// `CopyObject` for sources above 5 GiB.
+const DEFAULT_SINGLE_COPY_THRESHOLD: u64 = 5 * 1024 * 1024 * 1024;
+
+/// Options controlling [`copy_object_large`](CopyObjectLargeOperations::copy_object_large).
+#[derive(Debug, Clone)]
+pub struct CopyObjectLargeOptions {
+    /// Source objects at or below this size are copied with a single `CopyObject` call
+    /// instead of a multipart copy.
+    pub single_copy_threshold: u64,
+    /// Size of each part copied, before the automatic growth that keeps the part count
+    /// at or below 10000.
+    pub part_size: u64,
+    /// Maximum number of `UploadPartCopy` requests in flight at the same time.
+    pub max_concurrency: usize,
+    /// Pin every `UploadPartCopy` call to the source object's ETag observed by the
+    /// initial `HeadObject`, so a mutation mid-copy surfaces as a 412 instead of
+    /// silently splicing together bytes from two different versions of the source.
+    pub pin_source_etag: bool,
+    /// Verify the copy by folding each part's `x-oss-hash-crc64ecma` (and, for a
+    /// single-shot copy, the destination's own) into a whole-object CRC and comparing it
+    /// against the source's `x-oss-hash-crc64ecma` observed by the initial `HeadObject`.
+    pub verify_crc64: bool,
+    /// Options forwarded to the underlying `InitiateMultipartUpload` call.
+    pub initiate_options: Option<InitiateMultipartUploadOptions>,
+    /// Options forwarded to the underlying `CompleteMultipartUpload` call.
+    pub complete_options: Option<CompleteMultipartUploadOptions>,
+    /// Options forwarded to the underlying `CopyObject` call when falling back to a
+    /// single-shot copy.
+    pub copy_options: Option<CopyObjectOptions>,
+}
+
+impl Default for CopyObjectLargeOptions {
+    fn default() -> Self {
+        Self {
+            single_copy_threshold: DEFAULT_SINGLE_COPY_THRESHOLD,
+            part_size: DEFAULT_PART_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            pin_source_etag: true,
+            verify_crc64: false,
+            initiate_options: None,
+            complete_options: None,
+            copy_options: None,
+        }
+    }
+}
+
+impl CopyObjectLargeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn single_copy_threshold(mut self, threshold: u64) -> Self {
+        self.single_copy_threshold = threshold;
+        self
+    }
+
+    pub fn part_size(mut self, part_size: u64) -> Self {
+        self.part_size = part_size;
+        self
+    }
+
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    pub fn pin_source_etag(mut self, pin_source_etag: bool) -> Self {
+        self.pin_source_etag = pin_source_etag;
+        self
+    }
+
+    pub fn verify_crc64(mut self, verify_crc64: bool) -> Self {
+        self.verify_crc64 = verify_crc64;
+        self
+    }
+
+    pub fn initiate_options(mut self, options: InitiateMultipartUploadOptions) -> Self {
+        self.initiate_options = Some(options);
+        self
+    }
+
+    pub fn complete_options(mut self, options: CompleteMultipartUploadOptions) -> Self {
+        self.complete_options = Some(options);
+        self
+    }
+
+    pub fn copy_options(mut self, options: CopyObjectOptions) -> Self {
+        self.copy_options = Some(options);
+        self
+    }
+}
+
+/// Outcome of [`copy_object_large`](CopyObjectLargeOperations::copy_object_large).
+#[derive(Debug, Clone)]
+pub enum CopyObjectLargeResult {
+    /// The source was at or below the threshold, so a single `CopyObject` call was used.
+    Single(CopyObjectResult),
+    /// The source was above the threshold, so a full multipart copy was driven.
+    Multipart {
+        /// The upload ID of the multipart upload that was driven to completion.
+        upload_id: String,
+        /// The result of the final `CompleteMultipartUpload` call.
+        complete: CompleteMultipartUploadResult,
+    },
+}
+
+/// Split `[0, size)` into an ascending list of inclusive byte ranges, growing `part_size`
+/// as needed so the result never exceeds `MAX_PARTS` ranges.
+fn plan_ranges(size: u64, part_size: u64) -> Vec<(u64, u64)> {
+    let mut part_size = part_size.max(MIN_PART_SIZE);
+    if size.div_ceil(part_size) > MAX_PARTS {
+        part_size = size.div_ceil(MAX_PARTS);
+    }
+
+    let mut ranges = Vec::new();
+    let mut start = 0u64;
+    while start < size {
+        let end = (start + part_size - 1).min(size - 1);
+        ranges.push((start, end));
+        start = end + 1;
+    }
+    ranges
+}
+
+async fn copy_all_parts(
+    client: &Client,
+    object_key: &str,
+    upload_id: &str,
+    source_bucket: &str,
+    source_key: &str,
+    ranges: &[(u64, u64)],
+    max_concurrency: usize,
+    source_etag: Option<&str>,
+) -> Result<Vec<(Part, Option<u64>)>> {
+    let mut in_flight = FuturesUnordered::new();
+    let mut parts = Vec::new();
+    let mut next_range = 0usize;
+
+    while next_range < ranges.len() || !in_flight.is_empty() {
+        while next_range < ranges.len() && in_flight.len() < max_concurrency {
+            let part_number = next_range as u32 + 1;
+            let (start, end) = ranges[next_range];
+            next_range += 1;
+
+            let client = client.clone();
+            let object_key = object_key.to_string();
+            let upload_id = upload_id.to_string();
+            let source_bucket = source_bucket.to_string();
+            let source_key = source_key.to_string();
+            let mut options = UploadPartCopyOptions::new().copy_source_range(start, end);
+            if let Some(etag) = source_etag {
+                options = options.copy_source_if_match(etag);
+            }
+            in_flight.push(tokio::spawn(async move {
+                let result = client
+                    .upload_part_copy(object_key, upload_id, part_number, source_bucket, source_key, Some(options))
+                    .await?;
+                let crc64 = result.hash_crc64ecma;
+                Ok::<(Part, Option<u64>), Error>((Part::from(result), crc64))
+            }));
+        }
+
+        match in_flight.next().await {
+            Some(joined) => {
+                let part = joined.map_err(|err| Error::Other(err.to_string()))??;
+                parts.push(part);
+            },
+            None => break,
+        }
+    }
+
+    parts.sort_by_key(|(part, _)| part.part_number);
+    Ok(parts)
+}
+
+/// Fold each part's CRC64-ECMA checksum into a single whole-object CRC, the way OSS
+/// computes it server-side, so it can be compared against the source object's checksum.
+/// Returns `None` if any part is missing its checksum.
+fn combine_part_crc64s(parts: &[(Part, Option<u64>)], ranges: &[(u64, u64)]) -> Option<u64> {
+    let mut combined: Option<u64> = None;
+    for ((_, crc64), (start, end)) in parts.iter().zip(ranges.iter()) {
+        let crc64 = (*crc64)?;
+        let len = end - start + 1;
+        combined = Some(match combined {
+            Some(prev) => crc64_combine(prev, crc64, len),
+            None => crc64,
+        });
+    }
+    combined
+}
+
+/// Trait for the high-level, size-aware object copy operation.
+pub trait CopyObjectLargeOperations {
+    /// Copy `source_key` to `target_key`, transparently choosing between a single-shot
+    /// `CopyObject` and a full server-side multipart copy based on the source object's
+    /// size.
+    ///
+    /// A `HeadObject` call first learns the source's size and ETag. If the size is at or
+    /// below `options.single_copy_threshold`, a plain `CopyObject` is issued. Otherwise an
+    /// `InitiateMultipartUpload` is started against `target_key`, `[0, size)` is split into
+    /// parts (growing the part size automatically so the part count never exceeds OSS's
+    /// 10000-part limit), and up to `max_concurrency` `UploadPartCopy` requests copy the
+    /// parts concurrently before the upload is completed. Every `UploadPartCopy` call is
+    /// pinned to the source's observed ETag by default, so a mutation mid-copy surfaces as
+    /// a 412 instead of silently stitching together bytes from two different versions of
+    /// the source. With [`CopyObjectLargeOptions::verify_crc64`], the per-part
+    /// `x-oss-hash-crc64ecma` values are folded into a whole-object CRC (the same way OSS
+    /// computes it server-side) and compared against the source's CRC. On any error, the
+    /// multipart upload is aborted via `AbortMultipartUpload` before the error is returned.
+    fn copy_object_large(
+        &self,
+        source_bucket: impl Into<String>,
+        source_key: impl Into<String>,
+        target_key: impl Into<String>,
+        options: Option<CopyObjectLargeOptions>,
+    ) -> impl Future<Output = Result<CopyObjectLargeResult>>;
+}
+
+impl CopyObjectLargeOperations for Client {
+    async fn copy_object_large(
+        &self,
+        source_bucket: impl Into<String>,
+        source_key: impl Into<String>,
+        target_key: impl Into<String>,
+        options: Option<CopyObjectLargeOptions>,
+    ) -> Result<CopyObjectLargeResult> {
+        let source_bucket = source_bucket.into();
+        let source_key = source_key.into();
+        let target_key = target_key.into();
+        let options = options.unwrap_or_default();
+        let max_concurrency = options.max_concurrency.max(1);
+
+        let head = self.head_object(&source_key, HeadObjectParams::default(), None).await?;
+        let source_crc64: Option<u64> = head.hash_crc64ecma.as_deref().and_then(|crc| crc.parse().ok());
+
+        if head.content_length <= options.single_copy_threshold {
+            let mut copy_options = options.copy_options.unwrap_or_default();
+            if options.verify_crc64 {
+                if let Some(expected) = source_crc64 {
+                    copy_options = copy_options.expected_crc64(expected);
+                }
+            }
+            let result = self
+                .copy_object(source_bucket, source_key, target_key, Some(copy_options))
+                .await?;
+            return Ok(CopyObjectLargeResult::Single(result));
+        }
+
+        let source_etag = options.pin_source_etag.then(|| head.etag.clone()).flatten();
+
+        let initiated = self
+            .initiate_multipart_upload(target_key.clone(), options.initiate_options)
+            .await?;
+        let upload_id = initiated.upload_id;
+
+        let ranges = plan_ranges(head.content_length, options.part_size);
+
+        let copied = match copy_all_parts(
+            self,
+            &target_key,
+            &upload_id,
+            &source_bucket,
+            &source_key,
+            &ranges,
+            max_concurrency,
+            source_etag.as_deref(),
+        )
+        .await
+        {
+            Ok(copied) => copied,
+            Err(err) => {
+                let _ = self.abort_multipart_upload(target_key, upload_id).await;
+                return Err(err);
+            },
+        };
+
+        if options.verify_crc64 {
+            if let (Some(expected), Some(computed)) = (source_crc64, combine_part_crc64s(&copied, &ranges)) {
+                if expected != computed {
+                    let _ = self.abort_multipart_upload(target_key, upload_id).await;
+                    return Err(Error::Crc64Mismatch {
+                        expected: expected.to_string(),
+                        computed: computed.to_string(),
+                    });
+                }
+            }
+        }
+
+        let parts = copied.into_iter().map(|(part, _)| part).collect();
+
+        match self
+            .complete_multipart_upload(target_key.clone(), upload_id.clone(), parts, options.complete_options)
+            .await
+        {
+            Ok(complete) => Ok(CopyObjectLargeResult::Multipart { upload_id, complete }),
+            Err(err) => {
+                let _ = self.abort_multipart_upload(target_key, upload_id).await;
+                Err(err)
+            },
+        }
+    }
+}
@@ -6,6 +6,7 @@ use http::{HeaderMap, HeaderName, Method, header};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
+use super::super::base::SseCustomerKey;
 use crate::body::ZeroBody;
 use crate::error::Result;
 use crate::response::BodyResponseProcessor;
@@ -125,15 +126,60 @@ pub struct InitiateMultipartUploadOptions {
     pub server_side_data_encryption: Option<String>,
     /// User master key managed by KMS
     pub server_side_encryption_key_id: Option<String>,
+    /// Customer-provided SSE-C key; `UploadPart`/`CompleteMultipartUpload`/`GetObject`
+    /// must be called with the same key to read the finished object back.
+    pub sse_customer_key: Option<SseCustomerKey>,
     /// Storage class
     pub storage_class: Option<StorageClass>,
     /// Object tags
     pub tagging: Option<String>,
     /// User-defined metadata
     pub user_meta: HashMap<String, String>,
+    /// Only create the object if its ETag matches (rarely useful here since the object
+    /// doesn't exist yet; mainly for symmetry with `GetObject`/`PutSymlink`)
+    pub if_match: Option<String>,
+    /// Only create the object if its ETag does not match, e.g. `"*"` to fail if any object
+    /// already exists at this key
+    pub if_none_match: Option<String>,
+    /// Only create the object if it was modified after this time
+    pub if_modified_since: Option<String>,
+    /// Only create the object if it was not modified after this time
+    pub if_unmodified_since: Option<String>,
 }
 
 impl InitiateMultipartUploadOptions {
+    /// Encrypt the object with a customer-provided 256-bit AES key (SSE-C). OSS never
+    /// stores the key; it must be supplied again on every follow-up request for this
+    /// object (`UploadPart`, `CompleteMultipartUpload`, `GetObject`, ...).
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Set the If-Match header
+    pub fn if_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    /// Set the If-None-Match header
+    pub fn if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Set the If-Modified-Since header
+    pub fn if_modified_since(mut self, time: impl Into<String>) -> Self {
+        self.if_modified_since = Some(time.into());
+        self
+    }
+
+    /// Set the If-Unmodified-Since header
+    pub fn if_unmodified_since(mut self, time: impl Into<String>) -> Self {
+        self.if_unmodified_since = Some(time.into());
+        self
+    }
+
     fn into_headers(self) -> Result<HeaderMap> {
         let mut headers = HeaderMap::new();
 
@@ -193,6 +239,11 @@ impl InitiateMultipartUploadOptions {
             );
         }
 
+        // Set SSE-C (customer-provided key) headers
+        if let Some(sse_customer_key) = &self.sse_customer_key {
+            sse_customer_key.insert_headers(&mut headers)?;
+        }
+
         // Set Storage class
         if let Some(storage_class) = self.storage_class {
             headers.insert(HeaderName::from_static("x-oss-storage-class"), storage_class.as_ref().parse()?);
@@ -210,6 +261,22 @@ impl InitiateMultipartUploadOptions {
             headers.insert(HeaderName::from_bytes(header_name.as_bytes())?, value.parse()?);
         }
 
+        if let Some(if_match) = self.if_match {
+            headers.insert(header::IF_MATCH, if_match.parse()?);
+        }
+
+        if let Some(if_none_match) = self.if_none_match {
+            headers.insert(header::IF_NONE_MATCH, if_none_match.parse()?);
+        }
+
+        if let Some(if_modified_since) = self.if_modified_since {
+            headers.insert(header::IF_MODIFIED_SINCE, if_modified_since.parse()?);
+        }
+
+        if let Some(if_unmodified_since) = self.if_unmodified_since {
+            headers.insert(header::IF_UNMODIFIED_SINCE, if_unmodified_since.parse()?);
+        }
+
         Ok(headers)
     }
 }
@@ -351,6 +418,12 @@ impl InitiateMultipartUploadRequestBuilder {
         self
     }
 
+    /// Encrypt the object with a customer-provided 256-bit AES key (SSE-C)
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.options.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
     /// Set storage class
     pub fn storage_class(mut self, storage_class: StorageClass) -> Self {
         self.options.storage_class = Some(storage_class);
@@ -369,6 +442,30 @@ impl InitiateMultipartUploadRequestBuilder {
         self
     }
 
+    /// Set the If-Match header
+    pub fn if_match(mut self, etag: impl Into<String>) -> Self {
+        self.options.if_match = Some(etag.into());
+        self
+    }
+
+    /// Set the If-None-Match header
+    pub fn if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.options.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Set the If-Modified-Since header
+    pub fn if_modified_since(mut self, time: impl Into<String>) -> Self {
+        self.options.if_modified_since = Some(time.into());
+        self
+    }
+
+    /// Set the If-Unmodified-Since header
+    pub fn if_unmodified_since(mut self, time: impl Into<String>) -> Self {
+        self.options.if_unmodified_since = Some(time.into());
+        self
+    }
+
     /// Build request options
     pub fn build(self) -> InitiateMultipartUploadOptions {
         self.options
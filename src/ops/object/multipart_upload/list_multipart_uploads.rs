@@ -1,10 +1,12 @@
 use std::future::Future;
 
+use futures::Stream;
 use http::Method;
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::body::NoneBody;
 use crate::error::Result;
+use crate::ops::pagination::paginate;
 use crate::response::BodyResponseProcessor;
 use crate::ser::OnlyKeyField;
 use crate::{Client, Ops, Prepared, Request};
@@ -196,11 +198,22 @@ impl Ops for ListMultipartUploads {
 pub trait ListMultipartUploadsOperations {
     /// List multipart upload events
     ///
+    /// This only enumerates in-flight uploads; see the sibling `InitiateMultipartUpload`,
+    /// `UploadPart`/`UploadPartCopy`, `CompleteMultipartUpload`, `AbortMultipartUpload`, and
+    /// `ListParts` operations in this module to create, finish, or clean one up.
+    ///
     /// Official documentation: <https://www.alibabacloud.com/help/en/oss/developer-reference/listmultipartuploads>
     fn list_multipart_uploads(
         &self,
         params: Option<ListMultipartUploadsParams>,
     ) -> impl Future<Output = Result<ListMultipartUploadsResult>>;
+
+    /// Walk every page of a ListMultipartUploads listing, following `next_key_marker`/
+    /// `next_upload_id_marker` until `is_truncated` is false.
+    fn list_multipart_uploads_stream(
+        &self,
+        params: Option<ListMultipartUploadsParams>,
+    ) -> impl Stream<Item = Result<ListMultipartUploadsResult>> + Send + 'static;
 }
 
 impl ListMultipartUploadsOperations for Client {
@@ -213,6 +226,28 @@ impl ListMultipartUploadsOperations for Client {
         };
         self.request(ops).await
     }
+
+    fn list_multipart_uploads_stream(
+        &self,
+        params: Option<ListMultipartUploadsParams>,
+    ) -> impl Stream<Item = Result<ListMultipartUploadsResult>> + Send + 'static {
+        let client = self.clone();
+        let base_params = params.unwrap_or_default();
+        let seed = (base_params.key_marker.clone(), base_params.upload_id_marker.clone());
+        paginate(
+            Some(seed),
+            move |marker| {
+                let (key_marker, upload_id_marker) = marker.unwrap_or((None, None));
+                let client = client.clone();
+                let mut params = base_params.clone();
+                params.key_marker = key_marker;
+                params.upload_id_marker = upload_id_marker;
+                async move { client.list_multipart_uploads(Some(params)).await }
+            },
+            |page| page.is_truncated,
+            |page| Some((page.next_key_marker.clone(), page.next_upload_id_marker.clone())),
+        )
+    }
 }
 
 // =============================================================================
@@ -1,11 +1,13 @@
 use std::future::Future;
 
+use futures::Stream;
 use http::Method;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
 use crate::body::NoneBody;
 use crate::error::Result;
+use crate::ops::pagination::paginate;
 use crate::response::BodyResponseProcessor;
 use crate::{Client, Ops, Prepared, Request};
 
@@ -120,6 +122,15 @@ pub trait ListPartsOperations {
         upload_id: impl Into<String>,
         params: Option<ListPartsParams>,
     ) -> impl Future<Output = Result<ListPartsResult>>;
+
+    /// Walk every page of a ListParts listing, following `next_part_number_marker`
+    /// until `is_truncated` is false.
+    fn list_parts_stream(
+        &self,
+        object_key: impl Into<String>,
+        upload_id: impl Into<String>,
+        params: Option<ListPartsParams>,
+    ) -> impl Stream<Item = Result<ListPartsResult>> + Send + 'static;
 }
 
 impl ListPartsOperations for Client {
@@ -137,6 +148,30 @@ impl ListPartsOperations for Client {
         };
         self.request(ops).await
     }
+
+    fn list_parts_stream(
+        &self,
+        object_key: impl Into<String>,
+        upload_id: impl Into<String>,
+        params: Option<ListPartsParams>,
+    ) -> impl Stream<Item = Result<ListPartsResult>> + Send + 'static {
+        let client = self.clone();
+        let object_key = object_key.into();
+        let base_params = params.unwrap_or_else(|| ListPartsParams::new(upload_id));
+        paginate(
+            base_params.part_number_marker,
+            move |part_number_marker| {
+                let client = client.clone();
+                let object_key = object_key.clone();
+                let mut params = base_params.clone();
+                params.part_number_marker = part_number_marker;
+                let upload_id = params.upload_id.clone();
+                async move { client.list_parts(object_key, upload_id, Some(params)).await }
+            },
+            |page| page.is_truncated,
+            |page| Some(page.next_part_number_marker),
+        )
+    }
 }
 
 // =============================================================================
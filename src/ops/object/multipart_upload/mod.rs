@@ -1,15 +1,23 @@
 mod abort_multipart_upload;
 mod complete_multipart_upload;
+mod copy_object_large;
 mod initiate_multipart_upload;
 mod list_multipart_uploads;
 mod list_parts;
+mod multipart_checkpoint;
+mod multipart_writer;
+mod put_object_multipart;
 mod upload_part;
 mod upload_part_copy;
 
 pub use abort_multipart_upload::*;
 pub use complete_multipart_upload::*;
+pub use copy_object_large::*;
 pub use initiate_multipart_upload::*;
 pub use list_multipart_uploads::*;
 pub use list_parts::*;
+pub use multipart_checkpoint::*;
+pub use multipart_writer::*;
+pub use put_object_multipart::*;
 pub use upload_part::*;
 pub use upload_part_copy::*;
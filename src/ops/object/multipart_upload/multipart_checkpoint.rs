@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    CompleteMultipartUploadOperations, CompleteMultipartUploadOptions, CompleteMultipartUploadResult,
+    ListPartsOperations, ListPartsParams, Part, UploadPartOperations, UploadPartOptions,
+};
+use crate::Client;
+use crate::error::Result;
+
+/// Serializable state for a resumable multipart upload: the upload ID, object key, chunk
+/// size, and the set of parts already confirmed by the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipartCheckpoint {
+    pub object_key: String,
+    pub upload_id: String,
+    pub part_size: usize,
+    pub confirmed_parts: Vec<Part>,
+}
+
+impl MultipartCheckpoint {
+    pub fn new(object_key: impl Into<String>, upload_id: impl Into<String>, part_size: usize) -> Self {
+        Self {
+            object_key: object_key.into(),
+            upload_id: upload_id.into(),
+            part_size,
+            confirmed_parts: Vec::new(),
+        }
+    }
+
+    /// Record a successfully-uploaded part, replacing any earlier record for the same
+    /// part number.
+    pub fn record_part(&mut self, part: Part) {
+        self.confirmed_parts.retain(|existing| existing.part_number != part.part_number);
+        self.confirmed_parts.push(part);
+        self.confirmed_parts.sort_by_key(|part| part.part_number);
+    }
+
+    pub fn is_part_confirmed(&self, part_number: u32) -> bool {
+        self.confirmed_parts.iter().any(|part| part.part_number == part_number)
+    }
+
+    /// Page through a live `ListParts` response and drop any locally-recorded part that
+    /// the server doesn't have (or whose ETag no longer matches), returning the parts
+    /// that need to be re-uploaded.
+    pub async fn reconcile(&mut self, client: &Client) -> Result<Vec<Part>> {
+        let mut live_parts = HashMap::new();
+        let mut part_number_marker = None;
+        loop {
+            let mut params = ListPartsParams::new(&self.upload_id).max_parts(1000);
+            if let Some(marker) = part_number_marker {
+                params = params.part_number_marker(marker);
+            }
+
+            let listing = client.list_parts(&self.object_key, &self.upload_id, Some(params)).await?;
+            for part in listing.parts {
+                live_parts.insert(part.part_number, part.etag);
+            }
+
+            if !listing.is_truncated {
+                break;
+            }
+            part_number_marker = Some(listing.next_part_number_marker);
+        }
+
+        let mut missing = Vec::new();
+        self.confirmed_parts.retain(|part| {
+            let confirmed_by_server = live_parts.get(&part.part_number) == Some(&part.etag);
+            if !confirmed_by_server {
+                missing.push(part.clone());
+            }
+            confirmed_by_server
+        });
+
+        Ok(missing)
+    }
+}
+
+/// Pluggable persistence for a [`MultipartCheckpoint`], so long-running uploads survive
+/// process restarts.
+pub trait CheckpointStore {
+    fn load(&self) -> impl Future<Output = Result<Option<MultipartCheckpoint>>>;
+
+    fn save(&self, checkpoint: &MultipartCheckpoint) -> impl Future<Output = Result<()>>;
+
+    fn clear(&self) -> impl Future<Output = Result<()>>;
+}
+
+/// A [`CheckpointStore`] backed by a single JSON file on disk.
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self) -> Result<Option<MultipartCheckpoint>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn save(&self, checkpoint: &MultipartCheckpoint) -> Result<()> {
+        let bytes = serde_json::to_vec_pretty(checkpoint)?;
+        tokio::fs::write(&self.path, bytes).await?;
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<()> {
+        match tokio::fs::remove_file(&self.path).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Trait for resuming an interrupted multipart upload from a checkpoint.
+pub trait MultipartResumeOperations {
+    /// Skip parts already recorded in `checkpoint`, upload `missing_parts`, and complete
+    /// the upload. `checkpoint` is persisted to `store` after each part is confirmed, so a
+    /// process that crashes partway through only has to re-upload the parts confirmed since
+    /// the last save, not the whole object.
+    fn resume_multipart_upload<T, S>(
+        &self,
+        checkpoint: &mut MultipartCheckpoint,
+        store: &S,
+        missing_parts: Vec<(u32, T)>,
+        part_options: Option<UploadPartOptions>,
+        options: Option<CompleteMultipartUploadOptions>,
+    ) -> impl Future<Output = Result<CompleteMultipartUploadResult>>
+    where
+        T: Send + 'static,
+        Bytes: From<T>,
+        S: CheckpointStore;
+}
+
+impl MultipartResumeOperations for Client {
+    async fn resume_multipart_upload<T, S>(
+        &self,
+        checkpoint: &mut MultipartCheckpoint,
+        store: &S,
+        missing_parts: Vec<(u32, T)>,
+        part_options: Option<UploadPartOptions>,
+        options: Option<CompleteMultipartUploadOptions>,
+    ) -> Result<CompleteMultipartUploadResult>
+    where
+        T: Send + 'static,
+        Bytes: From<T>,
+        S: CheckpointStore,
+    {
+        for (part_number, data) in missing_parts {
+            if checkpoint.is_part_confirmed(part_number) {
+                continue;
+            }
+
+            let result = self
+                .upload_part(
+                    checkpoint.object_key.clone(),
+                    checkpoint.upload_id.clone(),
+                    part_number,
+                    data,
+                    part_options.clone(),
+                )
+                .await?;
+            checkpoint.record_part(Part::new(part_number, result.etag));
+            store.save(checkpoint).await?;
+        }
+
+        self.complete_multipart_upload(
+            checkpoint.object_key.clone(),
+            checkpoint.upload_id.clone(),
+            checkpoint.confirmed_parts.clone(),
+            options,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `resume_multipart_upload` saves `checkpoint` to its store after every confirmed
+    /// part, so a process that crashes between saves loses at most the parts uploaded
+    /// since the last one — never the whole upload. This drives that save/reload cycle
+    /// directly against `FileCheckpointStore`, standing in for a crash between part 1 and
+    /// part 2.
+    #[test]
+    fn store_reflects_confirmed_parts_after_each_save() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(async {
+            let path = std::env::temp_dir()
+                .join(format!("ossify-checkpoint-test-{}-{}.json", std::process::id(), line!()));
+            let store = FileCheckpointStore::new(&path);
+            let mut checkpoint = MultipartCheckpoint::new("key", "upload-id", 1024);
+
+            checkpoint.record_part(Part::new(1, "etag-1"));
+            store.save(&checkpoint).await.unwrap();
+
+            // Simulated crash: a fresh load sees only the part saved so far.
+            let reloaded = store.load().await.unwrap().expect("checkpoint was saved");
+            assert!(reloaded.is_part_confirmed(1));
+            assert!(!reloaded.is_part_confirmed(2));
+
+            checkpoint.record_part(Part::new(2, "etag-2"));
+            store.save(&checkpoint).await.unwrap();
+
+            let reloaded = store.load().await.unwrap().expect("checkpoint was saved");
+            assert!(reloaded.is_part_confirmed(1));
+            assert!(reloaded.is_part_confirmed(2));
+
+            store.clear().await.unwrap();
+            assert!(store.load().await.unwrap().is_none());
+        });
+    }
+}
@@ -0,0 +1,339 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Bytes, BytesMut};
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, StreamExt};
+use tokio::io::AsyncWrite;
+use tokio::task::JoinError;
+
+use super::{
+    AbortMultipartUploadOperations, CompleteMultipartUploadBody, CompleteMultipartUploadOperations,
+    CompleteMultipartUploadOptions, CompleteMultipartUploadResult, InitiateMultipartUploadOperations,
+    InitiateMultipartUploadOptions, Part, UploadPartOperations, UploadPartOptions,
+};
+use crate::Client;
+use crate::error::{Error, Result};
+
+/// The minimum size (in bytes) OSS accepts for any part other than the last one.
+const MIN_PART_SIZE: usize = 100 * 1024;
+
+/// Default chunk size used by [`MultipartWriter`] when none is configured.
+const DEFAULT_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Default number of `UploadPart` requests [`MultipartWriter`] keeps in flight at once.
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+/// Number of attempts made to upload a single part before giving up on it.
+const MAX_PART_ATTEMPTS: u32 = 3;
+
+/// Options controlling how [`MultipartWriter`] buffers and uploads data.
+#[derive(Debug, Clone)]
+pub struct MultipartWriterOptions {
+    /// Size of each buffered chunk flushed as an `UploadPart` request.
+    pub part_size: usize,
+    /// Maximum number of `UploadPart` requests in flight at the same time.
+    pub max_concurrency: usize,
+    /// Options forwarded to the underlying `InitiateMultipartUpload` call.
+    pub initiate_options: Option<InitiateMultipartUploadOptions>,
+    /// Options forwarded to each underlying `UploadPart` call.
+    pub part_options: Option<UploadPartOptions>,
+    /// Options forwarded to the underlying `CompleteMultipartUpload` call.
+    pub complete_options: Option<CompleteMultipartUploadOptions>,
+}
+
+impl Default for MultipartWriterOptions {
+    fn default() -> Self {
+        Self {
+            part_size: DEFAULT_PART_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            initiate_options: None,
+            part_options: None,
+            complete_options: None,
+        }
+    }
+}
+
+impl MultipartWriterOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the size of each buffered chunk. Values below the OSS minimum part size are
+    /// clamped when the writer is created.
+    pub fn part_size(mut self, part_size: usize) -> Self {
+        self.part_size = part_size;
+        self
+    }
+
+    /// Set the maximum number of `UploadPart` requests in flight at once.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Set the options used for the initial `InitiateMultipartUpload` call.
+    pub fn initiate_options(mut self, options: InitiateMultipartUploadOptions) -> Self {
+        self.initiate_options = Some(options);
+        self
+    }
+
+    /// Set the options used for each `UploadPart` call.
+    pub fn part_options(mut self, options: UploadPartOptions) -> Self {
+        self.part_options = Some(options);
+        self
+    }
+
+    /// Set the options used for the final `CompleteMultipartUpload` call.
+    pub fn complete_options(mut self, options: CompleteMultipartUploadOptions) -> Self {
+        self.complete_options = Some(options);
+        self
+    }
+}
+
+enum WriterState {
+    Writing,
+    Draining,
+    Completing(BoxFuture<'static, Result<CompleteMultipartUploadResult>>),
+    Done,
+}
+
+/// A streaming [`tokio::io::AsyncWrite`] sink that drives the init → upload-parts →
+/// complete multipart upload lifecycle, so callers can stream arbitrarily large objects
+/// without materializing them in memory.
+///
+/// Bytes written to the handle are buffered into fixed-size chunks and flushed as
+/// `UploadPart` requests on a bounded set of concurrent in-flight requests. The handle is
+/// not readable; call [`shutdown`](tokio::io::AsyncWriteExt::shutdown) to sort the
+/// collected parts and complete the upload. Dropping the writer without shutting it down
+/// aborts the multipart upload.
+pub struct MultipartWriter {
+    client: Client,
+    object_key: String,
+    upload_id: String,
+    part_size: usize,
+    max_concurrency: usize,
+    part_options: Option<UploadPartOptions>,
+    complete_options: Option<CompleteMultipartUploadOptions>,
+    buffer: BytesMut,
+    next_part_number: u32,
+    in_flight: FuturesUnordered<tokio::task::JoinHandle<Result<Part>>>,
+    completed_parts: Vec<Part>,
+    result: Option<CompleteMultipartUploadResult>,
+    state: WriterState,
+}
+
+impl MultipartWriter {
+    /// The upload ID of the multipart upload this writer is driving.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// The result of `CompleteMultipartUpload`, available once `shutdown()` has finished.
+    pub fn result(&self) -> Option<&CompleteMultipartUploadResult> {
+        self.result.as_ref()
+    }
+
+    fn spawn_chunk(&mut self, data: Bytes) {
+        let part_number = self.next_part_number;
+        self.next_part_number += 1;
+
+        let client = self.client.clone();
+        let object_key = self.object_key.clone();
+        let upload_id = self.upload_id.clone();
+        let part_options = self.part_options.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut last_err = None;
+            for attempt in 0..MAX_PART_ATTEMPTS {
+                let outcome = client
+                    .upload_part(object_key.clone(), upload_id.clone(), part_number, data.clone(), part_options.clone())
+                    .await;
+                match outcome {
+                    Ok(result) => return Ok(Part::new(part_number, result.etag)),
+                    Err(err) => {
+                        last_err = Some(err);
+                        if attempt + 1 < MAX_PART_ATTEMPTS {
+                            continue;
+                        }
+                    },
+                }
+            }
+            Err(last_err.expect("at least one upload attempt is made"))
+        });
+
+        self.in_flight.push(handle);
+    }
+
+    fn spawn_ready_chunks(&mut self) {
+        while self.buffer.len() >= self.part_size {
+            let chunk = self.buffer.split_to(self.part_size).freeze();
+            self.spawn_chunk(chunk);
+        }
+    }
+
+    fn spawn_final_chunk(&mut self) {
+        let chunk = std::mem::take(&mut self.buffer).freeze();
+        // Skip an empty trailing part unless it would be the only part of the upload.
+        if chunk.is_empty() && self.next_part_number > 1 {
+            return;
+        }
+        self.spawn_chunk(chunk);
+    }
+
+    fn record(&mut self, join_result: std::result::Result<Result<Part>, JoinError>) -> io::Result<()> {
+        let part = join_result
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        self.completed_parts.push(part);
+        Ok(())
+    }
+
+    fn drain_completed(&mut self, cx: &mut Context<'_>) -> io::Result<()> {
+        while let Poll::Ready(Some(join_result)) = Pin::new(&mut self.in_flight).poll_next(cx) {
+            self.record(join_result)?;
+        }
+        Ok(())
+    }
+}
+
+impl AsyncWrite for MultipartWriter {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.drain_completed(cx)?;
+
+        // Apply backpressure once we already have a full chunk buffered and no free slot
+        // to send it on, instead of growing the buffer without bound.
+        if this.buffer.len() >= this.part_size && this.in_flight.len() >= this.max_concurrency {
+            return match Pin::new(&mut this.in_flight).poll_next(cx) {
+                Poll::Ready(Some(join_result)) => {
+                    this.record(join_result)?;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                },
+                Poll::Ready(None) | Poll::Pending => Poll::Pending,
+            };
+        }
+
+        this.buffer.extend_from_slice(buf);
+        this.spawn_ready_chunks();
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.drain_completed(cx)?;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                WriterState::Writing => {
+                    this.spawn_final_chunk();
+                    this.state = WriterState::Draining;
+                },
+                WriterState::Draining => {
+                    this.drain_completed(cx)?;
+                    if !this.in_flight.is_empty() {
+                        return Poll::Pending;
+                    }
+
+                    let mut parts = std::mem::take(&mut this.completed_parts);
+                    parts.sort_by_key(|part| part.part_number);
+
+                    let client = this.client.clone();
+                    let object_key = this.object_key.clone();
+                    let upload_id = this.upload_id.clone();
+                    let options = this.complete_options.clone();
+                    this.state = WriterState::Completing(
+                        async move { client.complete_multipart_upload(object_key, upload_id, parts, options).await }
+                            .boxed(),
+                    );
+                },
+                WriterState::Completing(fut) => {
+                    return match fut.as_mut().poll(cx) {
+                        Poll::Ready(Ok(result)) => {
+                            this.result = Some(result);
+                            this.state = WriterState::Done;
+                            Poll::Ready(Ok(()))
+                        },
+                        Poll::Ready(Err(err)) => {
+                            this.state = WriterState::Done;
+                            Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, err)))
+                        },
+                        Poll::Pending => Poll::Pending,
+                    };
+                },
+                WriterState::Done => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
+
+impl Drop for MultipartWriter {
+    fn drop(&mut self) {
+        if matches!(self.state, WriterState::Done) {
+            return;
+        }
+
+        let client = self.client.clone();
+        let object_key = self.object_key.clone();
+        let upload_id = self.upload_id.clone();
+        tokio::spawn(async move {
+            let _ = client.abort_multipart_upload(object_key, upload_id).await;
+        });
+    }
+}
+
+/// Trait for the high-level streaming multipart upload operation.
+pub trait MultipartWriterOperations {
+    /// Open a [`MultipartWriter`] for `object_key`, initiating a new multipart upload.
+    ///
+    /// The returned handle implements [`tokio::io::AsyncWrite`]; call `shutdown()` once all
+    /// data has been written to complete the upload, or drop it to abort.
+    fn put_multipart(
+        &self,
+        object_key: impl Into<String>,
+        options: Option<MultipartWriterOptions>,
+    ) -> impl Future<Output = Result<MultipartWriter>>;
+}
+
+impl MultipartWriterOperations for Client {
+    async fn put_multipart(
+        &self,
+        object_key: impl Into<String>,
+        options: Option<MultipartWriterOptions>,
+    ) -> Result<MultipartWriter> {
+        let object_key = object_key.into();
+        let options = options.unwrap_or_default();
+
+        if options.max_concurrency == 0 {
+            return Err(Error::InvalidArgument("max_concurrency must be at least 1".to_string()));
+        }
+
+        let initiated = self
+            .initiate_multipart_upload(object_key.clone(), options.initiate_options)
+            .await?;
+
+        Ok(MultipartWriter {
+            client: self.clone(),
+            object_key,
+            upload_id: initiated.upload_id,
+            part_size: options.part_size.max(MIN_PART_SIZE),
+            max_concurrency: options.max_concurrency,
+            part_options: options.part_options,
+            complete_options: options.complete_options,
+            buffer: BytesMut::new(),
+            next_part_number: 1,
+            in_flight: FuturesUnordered::new(),
+            completed_parts: Vec::new(),
+            result: None,
+            state: WriterState::Writing,
+        })
+    }
+}
@@ -0,0 +1,435 @@
+use std::future::Future;
+
+use bytes::BytesMut;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::super::base::{ClientSideEncryptionKey, ClientSideEncryptor, ClientSideEncryptorSealer};
+use super::{
+    AbortMultipartUploadOperations, CompleteMultipartUploadOperations, CompleteMultipartUploadOptions,
+    CompleteMultipartUploadResult, InitiateMultipartUploadOperations, InitiateMultipartUploadOptions, Part,
+    UploadPartOperations, UploadPartOptions,
+};
+use crate::Client;
+use crate::error::{Error, Result};
+use crate::utils::{Crc64, crc64_combine};
+
+/// The minimum size (in bytes) OSS accepts for any part other than the last one.
+const MIN_PART_SIZE: usize = 100 * 1024;
+
+/// Default chunk size used by [`put_object_multipart`](PutObjectMultipartOperations::put_object_multipart).
+const DEFAULT_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Default number of `UploadPart` requests kept in flight at once.
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+/// Options controlling [`put_object_multipart`](PutObjectMultipartOperations::put_object_multipart).
+#[derive(Debug, Clone)]
+pub struct PutObjectMultipartOptions {
+    /// Size of each part read from the source and uploaded.
+    pub part_size: usize,
+    /// Maximum number of `UploadPart` requests in flight at the same time.
+    pub max_concurrency: usize,
+    /// Options forwarded to the underlying `InitiateMultipartUpload` call.
+    pub initiate_options: Option<InitiateMultipartUploadOptions>,
+    /// Options forwarded to each underlying `UploadPart` call.
+    pub part_options: Option<UploadPartOptions>,
+    /// Options forwarded to the underlying `CompleteMultipartUpload` call.
+    pub complete_options: Option<CompleteMultipartUploadOptions>,
+    /// Transparently encrypt the object client-side with AES-256-GCM before upload,
+    /// wrapping the freshly-generated content-encryption key under this master key. See
+    /// [`ClientSideEncryptionKey`]. When set, `part_size` is rounded up to a whole multiple
+    /// of the encryptor's chunk size, since every part except the last must align to a
+    /// chunk boundary for the part to be independently decryptable.
+    pub client_side_encryption_key: Option<ClientSideEncryptionKey>,
+    /// Verify each part's CRC64-ECMA checksum against the `UploadPart` response as it
+    /// completes, then fold the per-part checksums into a whole-object checksum (see
+    /// [`crate::utils::crc64_combine`]) and verify that against `CompleteMultipartUpload`'s
+    /// `x-oss-hash-crc64ecma`, failing with [`Error::Crc64Mismatch`] on a mismatch.
+    pub verify_crc64: bool,
+}
+
+impl Default for PutObjectMultipartOptions {
+    fn default() -> Self {
+        Self {
+            part_size: DEFAULT_PART_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            initiate_options: None,
+            part_options: None,
+            complete_options: None,
+            client_side_encryption_key: None,
+            verify_crc64: false,
+        }
+    }
+}
+
+impl PutObjectMultipartOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn part_size(mut self, part_size: usize) -> Self {
+        self.part_size = part_size;
+        self
+    }
+
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    pub fn initiate_options(mut self, options: InitiateMultipartUploadOptions) -> Self {
+        self.initiate_options = Some(options);
+        self
+    }
+
+    pub fn part_options(mut self, options: UploadPartOptions) -> Self {
+        self.part_options = Some(options);
+        self
+    }
+
+    pub fn complete_options(mut self, options: CompleteMultipartUploadOptions) -> Self {
+        self.complete_options = Some(options);
+        self
+    }
+
+    /// Transparently encrypt the object client-side with AES-256-GCM before upload,
+    /// wrapping the freshly-generated content-encryption key under `master_key`. The
+    /// wrapped key, base nonce and algorithm identifiers are stored as `x-oss-meta-*`
+    /// entries on the `InitiateMultipartUpload` call, so
+    /// [`GetObjectOptions::client_side_encryption_key`](super::super::base::GetObjectOptions::client_side_encryption_key)
+    /// can reverse it with the same `master_key`.
+    pub fn client_side_encryption_key(mut self, master_key: [u8; 32]) -> Self {
+        self.client_side_encryption_key = Some(ClientSideEncryptionKey::new(master_key));
+        self
+    }
+
+    /// Verify each part's CRC64-ECMA checksum as it's uploaded and the whole object's
+    /// combined checksum once the upload completes.
+    pub fn verify_crc64(mut self, verify: bool) -> Self {
+        self.verify_crc64 = verify;
+        self
+    }
+}
+
+/// Outcome of [`put_object_multipart`](PutObjectMultipartOperations::put_object_multipart).
+#[derive(Debug, Clone)]
+pub struct PutObjectMultipartResult {
+    /// The upload ID of the multipart upload that was driven to completion.
+    pub upload_id: String,
+    /// The result of the final `CompleteMultipartUpload` call.
+    pub complete: CompleteMultipartUploadResult,
+}
+
+/// Parse a decimal CRC64-ECMA checksum string, as reported by `UploadPart`/`CompleteMultipartUpload`.
+fn parse_crc64(value: &str) -> Result<u64> {
+    value.parse().map_err(|_| Error::Other(format!("invalid CRC64 checksum: {value}")))
+}
+
+/// Compare a locally-folded whole-object CRC64 against the one `CompleteMultipartUpload`
+/// reported, erroring on a mismatch. Either side being absent is not itself an error.
+fn verify_crc64(expected: Option<u64>, computed: Option<u64>) -> Result<()> {
+    if let (Some(expected), Some(computed)) = (expected, computed) {
+        if expected != computed {
+            return Err(Error::Crc64Mismatch {
+                expected: expected.to_string(),
+                computed: computed.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Read up to `part_size` bytes from `reader`, returning the bytes read and whether they
+/// reach the end of `reader`. A part that fills to exactly `part_size` doesn't yet know
+/// whether it's the last one: waiting for a zero-length read on the *next* call arrives one
+/// part too late, since by then this part has already been sealed as non-final. So once a
+/// part fills completely, peek one more byte to settle it now, stashing that byte in
+/// `pending_byte` for the next call when there turns out to be more data.
+async fn read_part<R>(reader: &mut R, part_size: usize, pending_byte: &mut Option<u8>) -> Result<(BytesMut, bool)>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffer = BytesMut::zeroed(part_size);
+    let mut filled = 0;
+
+    if let Some(byte) = pending_byte.take() {
+        buffer[0] = byte;
+        filled = 1;
+    }
+
+    let mut eof = false;
+    while filled < part_size {
+        let read = reader.read(&mut buffer[filled..]).await?;
+        if read == 0 {
+            eof = true;
+            break;
+        }
+        filled += read;
+    }
+    buffer.truncate(filled);
+
+    if !eof && filled == part_size {
+        let mut peek = [0u8; 1];
+        if reader.read(&mut peek).await? == 0 {
+            eof = true;
+        } else {
+            *pending_byte = Some(peek[0]);
+        }
+    }
+
+    Ok((buffer, eof))
+}
+
+/// Drives up to `max_concurrency` `UploadPart` requests at once via a bounded
+/// [`FuturesUnordered`], so memory stays at roughly `part_size * max_concurrency` instead of
+/// buffering the whole object, while still keeping bandwidth utilization high on large
+/// objects. Parts complete out of order but are sorted by `part_number` before returning, so
+/// callers don't need to track ordering themselves.
+///
+/// When `verify_crc64` is set, each part's checksum is checked against its `UploadPart`
+/// response as it completes (failing fast with [`Error::Crc64Mismatch`]), and the per-part
+/// checksums are folded in `part_number` order into a whole-object CRC64 via
+/// [`crc64_combine`], returned alongside the parts for the caller to verify against
+/// `CompleteMultipartUpload`'s reported checksum.
+async fn upload_all_parts<R>(
+    client: &Client,
+    object_key: &str,
+    upload_id: &str,
+    reader: &mut R,
+    part_size: usize,
+    max_concurrency: usize,
+    part_options: Option<UploadPartOptions>,
+    mut sealer: Option<ClientSideEncryptorSealer>,
+    verify_crc64: bool,
+) -> Result<(Vec<Part>, Option<u64>)>
+where
+    R: AsyncRead + Unpin + Send,
+{
+    let mut in_flight = FuturesUnordered::new();
+    let mut parts = Vec::new();
+    let mut next_part_number = 1u32;
+    let mut eof = false;
+    let mut pending_byte = None;
+
+    while !eof || !in_flight.is_empty() {
+        while !eof && in_flight.len() < max_concurrency {
+            let (buffer, is_last) = read_part(reader, part_size, &mut pending_byte).await?;
+            eof = is_last;
+            let filled = buffer.len();
+
+            // Skip an empty trailing chunk unless it would be the only part uploaded.
+            if filled == 0 && next_part_number > 1 {
+                break;
+            }
+
+            let data = match &mut sealer {
+                Some(sealer) => sealer.seal(&buffer, eof)?,
+                None => buffer.freeze(),
+            };
+            let part_number = next_part_number;
+            next_part_number += 1;
+
+            let part_crc = verify_crc64.then(|| {
+                let mut hasher = Crc64::new();
+                hasher.update(&data);
+                (hasher.digest(), data.len() as u64)
+            });
+
+            let client = client.clone();
+            let object_key = object_key.to_string();
+            let upload_id = upload_id.to_string();
+            let part_options = part_options.clone();
+            in_flight.push(tokio::spawn(async move {
+                let result = client
+                    .upload_part(object_key, upload_id, part_number, data, part_options)
+                    .await?;
+                if let Some((expected, _)) = &part_crc {
+                    if *expected != result.hash_crc64ecma {
+                        return Err(Error::Crc64Mismatch {
+                            expected: expected.clone(),
+                            computed: result.hash_crc64ecma,
+                        });
+                    }
+                }
+                Ok::<(Part, Option<(String, u64)>), Error>((Part::new(part_number, result.etag), part_crc))
+            }));
+
+            if eof {
+                break;
+            }
+        }
+
+        match in_flight.next().await {
+            Some(joined) => {
+                let part = joined.map_err(|err| Error::Other(err.to_string()))??;
+                parts.push(part);
+            },
+            None => break,
+        }
+    }
+
+    parts.sort_by_key(|(part, _)| part.part_number);
+
+    let whole_crc64 = if verify_crc64 {
+        let mut combined = 0u64;
+        for (_, part_crc) in &parts {
+            let (crc, len) = part_crc.as_ref().expect("verify_crc64 implies every part carries a checksum");
+            combined = crc64_combine(combined, parse_crc64(crc)?, *len);
+        }
+        Some(combined)
+    } else {
+        None
+    };
+
+    Ok((parts.into_iter().map(|(part, _)| part).collect(), whole_crc64))
+}
+
+/// Trait for the high-level, stream-driven multipart upload operation.
+pub trait PutObjectMultipartOperations {
+    /// Upload `reader` as `object_key` via multipart upload without buffering the whole
+    /// object in memory: the stream is split into fixed-size parts (enforcing the OSS
+    /// minimum part size except for the final part) and uploaded with up to
+    /// `max_concurrency` `UploadPart` requests in flight at once.
+    ///
+    /// This is the orchestrator that drives `InitiateMultipartUpload` → chunked `UploadPart`
+    /// → `CompleteMultipartUpload` end to end; set `Content-Type` and other object metadata
+    /// via [`PutObjectMultipartOptions::initiate_options`], since that's the request that
+    /// actually creates the object.
+    ///
+    /// On any part failure, the upload is aborted via `AbortMultipartUpload` to release
+    /// server-side storage before the error is returned.
+    fn put_object_multipart<R>(
+        &self,
+        object_key: impl Into<String>,
+        reader: R,
+        options: Option<PutObjectMultipartOptions>,
+    ) -> impl Future<Output = Result<PutObjectMultipartResult>>
+    where
+        R: AsyncRead + Unpin + Send + 'static;
+}
+
+impl PutObjectMultipartOperations for Client {
+    async fn put_object_multipart<R>(
+        &self,
+        object_key: impl Into<String>,
+        mut reader: R,
+        options: Option<PutObjectMultipartOptions>,
+    ) -> Result<PutObjectMultipartResult>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let object_key = object_key.into();
+        let mut options = options.unwrap_or_default();
+        let max_concurrency = options.max_concurrency.max(1);
+
+        let master_key = options.client_side_encryption_key.take();
+        let encryptor = master_key.as_ref().map(ClientSideEncryptor::generate).transpose()?;
+
+        // Chunks sealed by the encryptor are only independently decryptable if every part
+        // except the last is a whole multiple of its chunk size, so round up instead of
+        // clamping down like the plaintext path does.
+        let part_size = match &encryptor {
+            Some(_) => {
+                let chunk_size = ClientSideEncryptorSealer::chunk_size();
+                options.part_size.max(MIN_PART_SIZE).div_ceil(chunk_size) * chunk_size
+            },
+            None => options.part_size.max(MIN_PART_SIZE),
+        };
+
+        let mut initiate_options = options.initiate_options.unwrap_or_default();
+        if let Some(encryptor) = &encryptor {
+            initiate_options.user_meta.extend(encryptor.metadata());
+        }
+
+        let initiated = self
+            .initiate_multipart_upload(object_key.clone(), Some(initiate_options))
+            .await?;
+        let upload_id = initiated.upload_id;
+
+        let sealer = encryptor.map(ClientSideEncryptor::into_sealer);
+        let should_verify_crc64 = options.verify_crc64;
+
+        let (parts, whole_crc64) = match upload_all_parts(
+            self,
+            &object_key,
+            &upload_id,
+            &mut reader,
+            part_size,
+            max_concurrency,
+            options.part_options,
+            sealer,
+            should_verify_crc64,
+        )
+        .await
+        {
+            Ok(parts) => parts,
+            Err(err) => {
+                let _ = self.abort_multipart_upload(object_key, upload_id).await;
+                return Err(err);
+            },
+        };
+
+        match self
+            .complete_multipart_upload(object_key.clone(), upload_id.clone(), parts, options.complete_options)
+            .await
+        {
+            Ok(complete) => {
+                if should_verify_crc64 {
+                    verify_crc64(whole_crc64, complete.hash_crc64ecma)?;
+                }
+                Ok(PutObjectMultipartResult { upload_id, complete })
+            },
+            Err(err) => {
+                let _ = self.abort_multipart_upload(object_key, upload_id).await;
+                Err(err)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::super::super::base::{ClientSideDecryptor, ClientSideEncryptionKey, ClientSideEncryptor};
+    use super::*;
+
+    /// A source whose length is an exact multiple of `part_size` never observes a
+    /// zero-length read until a part boundary has already been sealed as non-final,
+    /// which would otherwise leave the true last chunk missing `LAST_CHUNK_AAD` and make
+    /// the object fail `ClientSideDecryptor` verification entirely.
+    #[test]
+    fn exact_multiple_of_part_size_seals_the_true_last_part() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(async {
+            let part_size = ClientSideEncryptorSealer::chunk_size();
+            let plaintext = vec![0x42u8; part_size * 2];
+
+            let master_key = ClientSideEncryptionKey::new([7u8; 32]);
+            let encryptor = ClientSideEncryptor::generate(&master_key).unwrap();
+            let metadata = encryptor.metadata();
+            let mut sealer = encryptor.into_sealer();
+
+            let mut reader = Cursor::new(plaintext.clone());
+            let mut pending_byte = None;
+            let mut sealed = BytesMut::new();
+            let mut eof = false;
+            while !eof {
+                let (buffer, is_last) = read_part(&mut reader, part_size, &mut pending_byte).await.unwrap();
+                eof = is_last;
+                sealed.extend_from_slice(&sealer.seal(&buffer, eof).unwrap());
+            }
+
+            let decryptor = ClientSideDecryptor::from_metadata(
+                &master_key,
+                metadata.get("client-side-encryption-key").unwrap(),
+                metadata.get("client-side-encryption-start").unwrap(),
+            )
+            .unwrap();
+            let decrypted = decryptor.decode_bytes(&sealed).unwrap();
+            assert_eq!(decrypted.as_ref(), plaintext.as_slice());
+        });
+    }
+}
@@ -1,13 +1,18 @@
-use std::convert::Infallible;
 use std::future::Future;
 
-use bytes::Bytes;
-use futures::{TryStream, stream};
-use http::Method;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use bytes::{Bytes, BytesMut};
+use futures::{TryStream, TryStreamExt};
+use http::{HeaderMap, HeaderName, Method};
+use md5::{Digest, Md5};
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
 
-use crate::body::StreamBody;
-use crate::error::Result;
+use super::super::base::SseCustomerKey;
+use crate::body::{BytesBody, StreamBody, box_byte_stream};
+use crate::error::{Error, Result};
 use crate::response::HeaderResponseProcessor;
 use crate::{BoxError, Client, Ops, Prepared, Request};
 
@@ -38,10 +43,58 @@ pub struct UploadPartResult {
     pub content_md5: String,
 }
 
+/// UploadPart request options (primarily set through HTTP headers)
+#[derive(Debug, Clone, Default)]
+pub struct UploadPartOptions {
+    /// The customer-provided SSE-C key the object was encrypted with, needed to encrypt
+    /// this part consistently with the rest of the upload.
+    pub sse_customer_key: Option<SseCustomerKey>,
+    /// Compute an MD5 digest of the part body and send it as `Content-MD5`, so OSS
+    /// rejects the part server-side if it arrives corrupted, then verify the `content_md5`
+    /// OSS echoes back in its response matches what was sent, failing with
+    /// [`Error::ContentMd5Mismatch`] on a mismatch.
+    ///
+    /// `upload_part_stream` can only do this by buffering the whole stream first, since
+    /// `Content-MD5` must be known before the body is sent; `upload_part`, which already
+    /// takes the body as a buffered value, pays no extra cost.
+    pub verify_content_md5: bool,
+}
+
+impl UploadPartOptions {
+    /// Encrypt the part with the customer-provided 256-bit AES key (SSE-C) the upload
+    /// was initiated with.
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Compute, send and verify `Content-MD5` for the part body.
+    pub fn verify_content_md5(mut self, verify: bool) -> Self {
+        self.verify_content_md5 = verify;
+        self
+    }
+
+    fn into_headers(self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+
+        // Set SSE-C (customer-provided key) headers
+        if let Some(sse_customer_key) = &self.sse_customer_key {
+            sse_customer_key.insert_headers(&mut headers)?;
+        }
+
+        Ok(headers)
+    }
+}
+
 /// UploadPart operation
 pub struct UploadPart<S> {
     pub object_key: String,
     pub params: UploadPartParams,
+    pub options: UploadPartOptions,
+    /// The base64-encoded MD5 digest of `stream_body`, precomputed by the caller when
+    /// `UploadPartOptions::verify_content_md5` is set (the digest must be known before the
+    /// body is sent, so it can't be computed from `stream_body` here).
+    pub content_md5: Option<String>,
     pub stream_body: S,
 }
 
@@ -56,16 +109,58 @@ where
     type Query = UploadPartParams;
 
     fn prepare(self) -> Result<Prepared<UploadPartParams, S>> {
+        let mut headers = self.options.into_headers()?;
+        if let Some(content_md5) = &self.content_md5 {
+            headers.insert(HeaderName::from_static("content-md5"), content_md5.parse()?);
+        }
         Ok(Prepared {
             method: Method::PUT,
             key: Some(self.object_key),
             query: Some(self.params),
+            headers: Some(headers),
             body: Some(self.stream_body),
             ..Default::default()
         })
     }
 }
 
+/// UploadPart operation for an in-memory part body.
+///
+/// `upload_part` uses this instead of [`UploadPart`] so the request ends up with a
+/// [`BytesBody`]-backed, clonable body: [`Client::request`]'s retry loop can only re-send
+/// a failed attempt when `reqwest::Request::try_clone` succeeds, which `StreamBody`'s
+/// wrapped stream never does even for a single known `Bytes` chunk. `upload_part_stream`,
+/// whose body genuinely can't be replayed, keeps using [`UploadPart`] and is attempted at
+/// most once as a result.
+pub struct UploadPartBytes {
+    pub object_key: String,
+    pub params: UploadPartParams,
+    pub options: UploadPartOptions,
+    pub content_md5: Option<String>,
+    pub body: Bytes,
+}
+
+impl Ops for UploadPartBytes {
+    type Response = HeaderResponseProcessor<UploadPartResult>;
+    type Body = BytesBody;
+    type Query = UploadPartParams;
+
+    fn prepare(self) -> Result<Prepared<UploadPartParams, Bytes>> {
+        let mut headers = self.options.into_headers()?;
+        if let Some(content_md5) = &self.content_md5 {
+            headers.insert(HeaderName::from_static("content-md5"), content_md5.parse()?);
+        }
+        Ok(Prepared {
+            method: Method::PUT,
+            key: Some(self.object_key),
+            query: Some(self.params),
+            headers: Some(headers),
+            body: Some(self.body),
+            ..Default::default()
+        })
+    }
+}
+
 /// Trait for UploadPart operations
 pub trait UploadPartOperations {
     /// Upload part
@@ -77,12 +172,16 @@ pub trait UploadPartOperations {
         upload_id: impl Into<String>,
         part_number: u32,
         body: T,
+        options: Option<UploadPartOptions>,
     ) -> impl Future<Output = Result<UploadPartResult>>
     where
         T: Send + 'static,
         Bytes: From<T>;
 
-    /// Upload part
+    /// Upload part from a stream
+    ///
+    /// If `options` sets [`UploadPartOptions::verify_content_md5`], the stream is fully
+    /// buffered first so its `Content-MD5` can be computed before the body is sent.
     ///
     /// Official documentation: <https://www.alibabacloud.com/help/en/oss/developer-reference/uploadpart>
     fn upload_part_stream<S>(
@@ -91,11 +190,30 @@ pub trait UploadPartOperations {
         upload_id: impl Into<String>,
         part_number: u32,
         stream: S,
+        options: Option<UploadPartOptions>,
     ) -> impl Future<Output = Result<UploadPartResult>>
     where
         S: TryStream + Send + 'static,
         S::Error: Into<BoxError>,
         Bytes: From<S::Ok>;
+
+    /// Upload part from an `AsyncRead` source (e.g. a file or socket), chunking reads
+    /// into `buffer_size`-byte pieces instead of requiring callers to hand-roll a
+    /// `TryStream<Bytes>` themselves. IO errors surface through the same `BoxError`
+    /// channel as `upload_part_stream`.
+    ///
+    /// Official documentation: <https://www.alibabacloud.com/help/en/oss/developer-reference/uploadpart>
+    fn upload_part_reader<R>(
+        &self,
+        object_key: impl Into<String>,
+        upload_id: impl Into<String>,
+        part_number: u32,
+        reader: R,
+        buffer_size: usize,
+        options: Option<UploadPartOptions>,
+    ) -> impl Future<Output = Result<UploadPartResult>>
+    where
+        R: AsyncRead + Unpin + Send + 'static;
 }
 
 impl UploadPartOperations for Client {
@@ -105,17 +223,33 @@ impl UploadPartOperations for Client {
         upload_id: impl Into<String>,
         part_number: u32,
         body: T,
+        options: Option<UploadPartOptions>,
     ) -> Result<UploadPartResult>
     where
         T: Send + 'static,
         Bytes: From<T>,
     {
-        let ops = UploadPart {
+        let options = options.unwrap_or_default();
+        let body = Bytes::from(body);
+        let content_md5 = options.verify_content_md5.then(|| BASE64_STANDARD.encode(Md5::digest(&body)));
+
+        let ops = UploadPartBytes {
             object_key: object_key.into(),
             params: UploadPartParams::new(part_number, upload_id),
-            stream_body: stream::once(async move { Result::<Bytes, Infallible>::Ok(body.into()) }),
+            options,
+            content_md5: content_md5.clone(),
+            body,
         };
-        self.request(ops).await
+        let result = self.request(ops).await?;
+        if let Some(expected) = content_md5 {
+            if expected != result.content_md5 {
+                return Err(Error::ContentMd5Mismatch {
+                    expected,
+                    computed: result.content_md5,
+                });
+            }
+        }
+        Ok(result)
     }
 
     async fn upload_part_stream<S>(
@@ -124,17 +258,53 @@ impl UploadPartOperations for Client {
         upload_id: impl Into<String>,
         part_number: u32,
         stream: S,
+        options: Option<UploadPartOptions>,
     ) -> Result<UploadPartResult>
     where
         S: TryStream + Send + 'static,
         S::Error: Into<BoxError>,
         Bytes: From<S::Ok>,
     {
+        let options = options.unwrap_or_default();
+
+        // `Content-MD5` must be known before the body is sent, so verifying it for a
+        // streaming part means buffering the stream first; callers who need true
+        // streaming should leave `verify_content_md5` unset.
+        if options.verify_content_md5 {
+            let body = box_byte_stream(stream)
+                .try_fold(BytesMut::new(), |mut buffer, chunk| async move {
+                    buffer.extend_from_slice(&chunk);
+                    Ok(buffer)
+                })
+                .await
+                .map_err(|err: BoxError| Error::Other(err.to_string()))?
+                .freeze();
+            return self.upload_part(object_key, upload_id, part_number, body, Some(options)).await;
+        }
+
         let ops = UploadPart {
             object_key: object_key.into(),
             params: UploadPartParams::new(part_number, upload_id),
+            options,
+            content_md5: None,
             stream_body: stream,
         };
         self.request(ops).await
     }
+
+    async fn upload_part_reader<R>(
+        &self,
+        object_key: impl Into<String>,
+        upload_id: impl Into<String>,
+        part_number: u32,
+        reader: R,
+        buffer_size: usize,
+        options: Option<UploadPartOptions>,
+    ) -> Result<UploadPartResult>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let stream = ReaderStream::with_capacity(reader, buffer_size);
+        self.upload_part_stream(object_key, upload_id, part_number, stream, options).await
+    }
 }
@@ -1,11 +1,15 @@
 use std::future::Future;
 
+use chrono::{DateTime, Utc};
 use http::{HeaderMap, HeaderName, Method};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
+use super::Part;
+use super::super::base::SseCustomerKey;
 use crate::body::ZeroBody;
-use crate::error::Result;
-use crate::response::BodyResponseProcessor;
+use crate::error::{Error, Result};
+use crate::response::BodyHeaderResponseProcessor;
+use crate::utils::format_http_date;
 use crate::{Client, Ops, Prepared, Request, escape_path};
 
 /// UploadPartCopy request parameters
@@ -38,6 +42,90 @@ pub struct UploadPartCopyOptions {
     pub copy_source_if_unmodified_since: Option<String>,
     /// Copy condition for source object: execute copy operation if source object was modified after the user-specified time
     pub copy_source_if_modified_since: Option<String>,
+    /// The customer-provided SSE-C key to encrypt the destination part with.
+    pub sse_customer_key: Option<SseCustomerKey>,
+    /// The customer-provided SSE-C key the source object was encrypted with, needed to
+    /// read it during the copy.
+    pub copy_source_sse_customer_key: Option<SseCustomerKey>,
+    /// If set, compare this value against the `x-oss-hash-crc64ecma` the response
+    /// reports (e.g. a part's slice of a `crc64_combine`-folded whole-object CRC) and
+    /// fail with [`Error::Crc64Mismatch`](crate::error::Error::Crc64Mismatch) on a mismatch.
+    pub expected_crc64: Option<u64>,
+}
+
+impl UploadPartCopyOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy only the inclusive byte range `[start, end]` from the source object, instead
+    /// of the whole object.
+    pub fn copy_source_range(mut self, start: u64, end: u64) -> Self {
+        self.copy_source_range = Some((start, end));
+        self
+    }
+
+    pub fn copy_source_if_match(mut self, etag: impl Into<String>) -> Self {
+        self.copy_source_if_match = Some(etag.into());
+        self
+    }
+
+    pub fn copy_source_if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.copy_source_if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Set copy source if modified since condition as a raw header value. Prefer
+    /// [`copy_source_if_modified_since_at`](Self::copy_source_if_modified_since_at), which
+    /// formats the timestamp the way OSS requires; this is an escape hatch for callers who
+    /// already have a pre-formatted HTTP-date string.
+    pub fn copy_source_if_modified_since(mut self, time: impl Into<String>) -> Self {
+        self.copy_source_if_modified_since = Some(time.into());
+        self
+    }
+
+    /// Set copy source if modified since condition, formatting `time` as the RFC 7231
+    /// HTTP-date OSS expects instead of requiring the caller to get the format right.
+    pub fn copy_source_if_modified_since_at(mut self, time: DateTime<Utc>) -> Self {
+        self.copy_source_if_modified_since = Some(format_http_date(time));
+        self
+    }
+
+    /// Set copy source if unmodified since condition as a raw header value. Prefer
+    /// [`copy_source_if_unmodified_since_at`](Self::copy_source_if_unmodified_since_at),
+    /// which formats the timestamp the way OSS requires; this is an escape hatch for
+    /// callers who already have a pre-formatted HTTP-date string.
+    pub fn copy_source_if_unmodified_since(mut self, time: impl Into<String>) -> Self {
+        self.copy_source_if_unmodified_since = Some(time.into());
+        self
+    }
+
+    /// Set copy source if unmodified since condition, formatting `time` as the RFC 7231
+    /// HTTP-date OSS expects instead of requiring the caller to get the format right.
+    pub fn copy_source_if_unmodified_since_at(mut self, time: DateTime<Utc>) -> Self {
+        self.copy_source_if_unmodified_since = Some(format_http_date(time));
+        self
+    }
+
+    /// Encrypt the destination part with the customer-provided 256-bit AES key (SSE-C).
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Decrypt the source object with the customer-provided 256-bit AES key (SSE-C) it
+    /// was uploaded with.
+    pub fn copy_source_sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.copy_source_sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Verify the part copy by comparing `expected` against the `x-oss-hash-crc64ecma`
+    /// the response reports.
+    pub fn expected_crc64(mut self, expected: u64) -> Self {
+        self.expected_crc64 = Some(expected);
+        self
+    }
 }
 
 impl UploadPartCopyOptions {
@@ -47,6 +135,18 @@ impl UploadPartCopyOptions {
         source_key: String,
         source_version_id: Option<String>,
     ) -> Result<HeaderMap> {
+        if self.copy_source_if_match.is_some() && self.copy_source_if_none_match.is_some() {
+            return Err(Error::InvalidArgument(
+                "copy_source_if_match and copy_source_if_none_match are mutually exclusive".to_string(),
+            ));
+        }
+        if self.copy_source_if_modified_since.is_some() && self.copy_source_if_unmodified_since.is_some() {
+            return Err(Error::InvalidArgument(
+                "copy_source_if_modified_since and copy_source_if_unmodified_since are mutually exclusive"
+                    .to_string(),
+            ));
+        }
+
         let mut headers = HeaderMap::new();
 
         let source_key = escape_path(&source_key);
@@ -91,6 +191,14 @@ impl UploadPartCopyOptions {
             );
         }
 
+        if let Some(sse_customer_key) = &self.sse_customer_key {
+            sse_customer_key.insert_headers(&mut headers)?;
+        }
+
+        if let Some(copy_source_sse_customer_key) = &self.copy_source_sse_customer_key {
+            copy_source_sse_customer_key.insert_copy_source_headers(&mut headers)?;
+        }
+
         Ok(headers)
     }
 }
@@ -105,13 +213,39 @@ pub struct CopyPartResult {
     pub last_modified: String,
 }
 
-/// UploadPartCopy response
+/// The XML body of an UploadPartCopy response
 #[derive(Debug, Clone, Deserialize)]
+struct UploadPartCopyResultBody {
+    copy_part_result: CopyPartResult,
+    part_number: u32,
+}
+
+fn deserialize_optional_crc64<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    s.map(|s| s.parse().map_err(serde::de::Error::custom)).transpose()
+}
+
+/// The subset of UploadPartCopy's response headers needed to verify integrity; kept
+/// separate from the user-facing result since the ETag/last-modified fields come back in
+/// the body.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct UploadPartCopyResultHeaders {
+    #[serde(rename = "x-oss-hash-crc64ecma", deserialize_with = "deserialize_optional_crc64", default)]
+    hash_crc64ecma: Option<u64>,
+}
+
+/// UploadPartCopy response
+#[derive(Debug, Clone)]
 pub struct UploadPartCopyResult {
     /// Copy result
     pub copy_part_result: CopyPartResult,
     /// Part number
     pub part_number: u32,
+    /// The CRC64-ECMA checksum of the part, as reported by `x-oss-hash-crc64ecma`.
+    pub hash_crc64ecma: Option<u64>,
 }
 
 /// UploadPartCopy operation
@@ -125,7 +259,7 @@ pub struct UploadPartCopy {
 }
 
 impl Ops for UploadPartCopy {
-    type Response = BodyResponseProcessor<UploadPartCopyResult>;
+    type Response = BodyHeaderResponseProcessor<UploadPartCopyResultBody, UploadPartCopyResultHeaders>;
     type Body = ZeroBody;
     type Query = UploadPartCopyParams;
 
@@ -149,6 +283,10 @@ impl Ops for UploadPartCopy {
 pub trait UploadPartCopyOperations {
     /// Upload part copy
     ///
+    /// To copy a part between customer-encrypted (SSE-C) objects, set
+    /// [`UploadPartCopyOptions::sse_customer_key`] for the destination and
+    /// [`UploadPartCopyOptions::copy_source_sse_customer_key`] for the source.
+    ///
     /// Official documentation: <https://www.alibabacloud.com/help/en/oss/developer-reference/uploadpartcopy>
     #[allow(clippy::too_many_arguments)]
     fn upload_part_copy(
@@ -177,6 +315,20 @@ pub trait UploadPartCopyOperations {
     ) -> impl Future<Output = Result<UploadPartCopyResult>>;
 }
 
+/// Compare an (optional) expected CRC64 against the one the server reported, erroring on
+/// a mismatch. Either side being absent is not itself an error.
+fn verify_crc64(expected: Option<u64>, computed: Option<u64>) -> Result<()> {
+    if let (Some(expected), Some(computed)) = (expected, computed) {
+        if expected != computed {
+            return Err(Error::Crc64Mismatch {
+                expected: expected.to_string(),
+                computed: computed.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
 impl UploadPartCopyOperations for Client {
     async fn upload_part_copy(
         &self,
@@ -187,15 +339,24 @@ impl UploadPartCopyOperations for Client {
         source_key: impl Into<String>,
         options: Option<UploadPartCopyOptions>,
     ) -> Result<UploadPartCopyResult> {
+        let options = options.unwrap_or_default();
+        let expected_crc64 = options.expected_crc64;
+
         let ops = UploadPartCopy {
             object_key: object_key.into(),
             source_bucket: source_bucket.into(),
             source_key: source_key.into(),
             source_version_id: None,
             params: UploadPartCopyParams::new(part_number, upload_id),
-            options: options.unwrap_or_default(),
+            options,
         };
-        self.request(ops).await
+        let (body, headers) = self.request(ops).await?;
+        verify_crc64(expected_crc64, headers.hash_crc64ecma)?;
+        Ok(UploadPartCopyResult {
+            copy_part_result: body.copy_part_result,
+            part_number: body.part_number,
+            hash_crc64ecma: headers.hash_crc64ecma,
+        })
     }
 
     async fn upload_part_copy_with_version_id(
@@ -208,15 +369,24 @@ impl UploadPartCopyOperations for Client {
         source_version_id: impl Into<String>,
         options: Option<UploadPartCopyOptions>,
     ) -> Result<UploadPartCopyResult> {
+        let options = options.unwrap_or_default();
+        let expected_crc64 = options.expected_crc64;
+
         let ops = UploadPartCopy {
             object_key: object_key.into(),
             source_bucket: source_bucket.into(),
             source_key: source_key.into(),
             source_version_id: Some(source_version_id.into()),
             params: UploadPartCopyParams::new(part_number, upload_id),
-            options: options.unwrap_or_default(),
+            options,
         };
-        self.request(ops).await
+        let (body, headers) = self.request(ops).await?;
+        verify_crc64(expected_crc64, headers.hash_crc64ecma)?;
+        Ok(UploadPartCopyResult {
+            copy_part_result: body.copy_part_result,
+            part_number: body.part_number,
+            hash_crc64ecma: headers.hash_crc64ecma,
+        })
     }
 }
 
@@ -245,3 +415,11 @@ impl From<UploadPartCopyResult> for PartCopyInfo {
         }
     }
 }
+
+/// Converts directly into a [`Part`], so results of server-side object composition via
+/// `UploadPartCopy` can be fed straight into `CompleteMultipartUploadBody`.
+impl From<UploadPartCopyResult> for Part {
+    fn from(result: UploadPartCopyResult) -> Self {
+        Part::new(result.part_number, result.copy_part_result.etag)
+    }
+}
@@ -1,9 +1,9 @@
 use std::future::Future;
 
-use http::{HeaderMap, HeaderName, Method};
+use http::{HeaderMap, HeaderName, Method, header};
 use serde::{Deserialize, Serialize};
 
-use super::super::base::StorageClass;
+use super::super::base::{SseCustomerKey, StorageClass};
 use crate::body::ZeroBody;
 use crate::error::Result;
 use crate::response::HeaderResponseProcessor;
@@ -34,6 +34,17 @@ pub struct PutSymlinkOptions {
     pub storage_class: Option<StorageClass>,
     /// Object access control list
     pub object_acl: Option<String>,
+    /// Customer-provided SSE-C key to encrypt the symlink object with.
+    pub sse_customer_key: Option<SseCustomerKey>,
+    /// Only create the symlink if its ETag matches
+    pub if_match: Option<String>,
+    /// Only create the symlink if its ETag does not match, e.g. `"*"` to fail if any object
+    /// already exists at this key
+    pub if_none_match: Option<String>,
+    /// Only create the symlink if it was modified after this time
+    pub if_modified_since: Option<String>,
+    /// Only create the symlink if it was not modified after this time
+    pub if_unmodified_since: Option<String>,
 }
 
 impl PutSymlinkOptions {
@@ -54,6 +65,36 @@ impl PutSymlinkOptions {
         self.object_acl = Some(acl.into());
         self
     }
+
+    /// Encrypt the symlink object with a customer-provided 256-bit AES key (SSE-C)
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Set the If-Match header
+    pub fn if_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    /// Set the If-None-Match header
+    pub fn if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Set the If-Modified-Since header
+    pub fn if_modified_since(mut self, time: impl Into<String>) -> Self {
+        self.if_modified_since = Some(time.into());
+        self
+    }
+
+    /// Set the If-Unmodified-Since header
+    pub fn if_unmodified_since(mut self, time: impl Into<String>) -> Self {
+        self.if_unmodified_since = Some(time.into());
+        self
+    }
 }
 
 impl PutSymlinkOptions {
@@ -78,6 +119,26 @@ impl PutSymlinkOptions {
             headers.insert(HeaderName::from_static("x-oss-object-acl"), object_acl.parse()?);
         }
 
+        if let Some(sse_customer_key) = &self.sse_customer_key {
+            sse_customer_key.insert_headers(&mut headers)?;
+        }
+
+        if let Some(if_match) = self.if_match {
+            headers.insert(header::IF_MATCH, if_match.parse()?);
+        }
+
+        if let Some(if_none_match) = self.if_none_match {
+            headers.insert(header::IF_NONE_MATCH, if_none_match.parse()?);
+        }
+
+        if let Some(if_modified_since) = self.if_modified_since {
+            headers.insert(header::IF_MODIFIED_SINCE, if_modified_since.parse()?);
+        }
+
+        if let Some(if_unmodified_since) = self.if_unmodified_since {
+            headers.insert(header::IF_UNMODIFIED_SINCE, if_unmodified_since.parse()?);
+        }
+
         Ok(headers)
     }
 }
@@ -182,6 +243,36 @@ impl PutSymlinkRequestBuilder {
         self
     }
 
+    /// Encrypt the symlink object with a customer-provided 256-bit AES key (SSE-C)
+    pub fn sse_customer_key(mut self, key: [u8; 32]) -> Self {
+        self.options.sse_customer_key = Some(SseCustomerKey::new(key));
+        self
+    }
+
+    /// Set the If-Match header
+    pub fn if_match(mut self, etag: impl Into<String>) -> Self {
+        self.options.if_match = Some(etag.into());
+        self
+    }
+
+    /// Set the If-None-Match header
+    pub fn if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.options.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Set the If-Modified-Since header
+    pub fn if_modified_since(mut self, time: impl Into<String>) -> Self {
+        self.options.if_modified_since = Some(time.into());
+        self
+    }
+
+    /// Set the If-Unmodified-Since header
+    pub fn if_unmodified_since(mut self, time: impl Into<String>) -> Self {
+        self.options.if_unmodified_since = Some(time.into());
+        self
+    }
+
     /// Build options
     pub fn build(self) -> PutSymlinkOptions {
         self.options
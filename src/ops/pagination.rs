@@ -0,0 +1,57 @@
+use std::future::Future;
+
+use futures::Stream;
+use futures::stream;
+
+use crate::error::Result;
+
+/// Drives a marker-based paginated list API as a lazily-polled [`Stream`] of pages.
+///
+/// `seed` is the marker for the first page (`None` to start from the beginning).
+/// `fetch` issues a single page request for a marker. `is_truncated`/`next_marker`
+/// pull the continuation state out of each page, so the same driving loop works for
+/// `ListBuckets`, `ListObjectsV2`, `ListMultipartUploads` and `ListParts` even though
+/// each API names and shapes its marker differently.
+///
+/// Each page is only fetched once the stream is polled, so arbitrarily long listings
+/// can be walked with bounded memory.
+pub(crate) fn paginate<T, M, Fetch, Fut>(
+    seed: Option<M>,
+    fetch: Fetch,
+    is_truncated: impl Fn(&T) -> bool + Send + 'static,
+    next_marker: impl Fn(&T) -> Option<M> + Send + 'static,
+) -> impl Stream<Item = Result<T>> + Send + 'static
+where
+    M: Send + 'static,
+    Fetch: Fn(Option<M>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T>> + Send,
+    T: Send + 'static,
+{
+    enum State<M> {
+        Next(Option<M>),
+        Done,
+    }
+
+    stream::unfold(State::Next(seed), move |state| {
+        let fetch = &fetch;
+        let is_truncated = &is_truncated;
+        let next_marker = &next_marker;
+        async move {
+            let marker = match state {
+                State::Next(marker) => marker,
+                State::Done => return None,
+            };
+            match fetch(marker).await {
+                Ok(page) => {
+                    let next_state = if is_truncated(&page) {
+                        State::Next(next_marker(&page))
+                    } else {
+                        State::Done
+                    };
+                    Some((Ok(page), next_state))
+                },
+                Err(err) => Some((Err(err), State::Done)),
+            }
+        }
+    })
+}
@@ -1,9 +1,13 @@
+use std::future::Future;
+
+use futures::Stream;
 use http::Method;
 use serde::{Deserialize, Deserializer, Serialize};
 
 use super::Owner;
 use crate::body::NoneBody;
 use crate::error::Result;
+use crate::ops::pagination::paginate;
 use crate::response::BodyResponseProcessor;
 use crate::{Client, Ops, Prepared, Request};
 
@@ -87,6 +91,14 @@ pub trait ServiceOperations {
         &self,
         params: Option<ListBucketsParams>,
     ) -> impl Future<Output = Result<ListAllMyBucketsResult>>;
+
+    /// Walk every page of a ListBuckets listing, following `next_marker` until
+    /// `is_truncated` is false. OSS sometimes omits `next_marker` on a truncated page,
+    /// so the last bucket's name on the page is used as the fallback marker.
+    fn list_buckets_stream(
+        &self,
+        params: Option<ListBucketsParams>,
+    ) -> impl Stream<Item = Result<ListAllMyBucketsResult>> + Send + 'static;
 }
 
 impl ServiceOperations for Client {
@@ -96,4 +108,23 @@ impl ServiceOperations for Client {
         };
         self.request(ops).await
     }
+
+    fn list_buckets_stream(
+        &self,
+        params: Option<ListBucketsParams>,
+    ) -> impl Stream<Item = Result<ListAllMyBucketsResult>> + Send + 'static {
+        let client = self.clone();
+        let base_params = params.unwrap_or_default();
+        paginate(
+            base_params.marker.clone(),
+            move |marker| {
+                let client = client.clone();
+                let mut params = base_params.clone();
+                params.marker = marker;
+                async move { client.list_buckets(Some(params)).await }
+            },
+            |page| page.is_truncated == Some(true),
+            |page| page.next_marker.clone().or_else(|| page.buckets.last().map(|bucket| bucket.name.clone())),
+        )
+    }
 }
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use reqwest::multipart::{Form, Part};
+
+use crate::error::Result;
+use crate::response::process_response_error;
+
+/// The handful of response headers OSS echoes back from a successful
+/// [`Client::post_object`](crate::Client::post_object) upload.
+#[derive(Debug, Clone, Default)]
+pub struct PostObjectResponse {
+    /// ETag of the uploaded object.
+    pub etag: Option<String>,
+    /// CRC64 value of the uploaded object.
+    pub hash_crc64ecma: Option<String>,
+}
+
+/// Execute the signed `multipart/form-data` upload [`Client::post_object`](crate::Client::post_object)
+/// builds: `fields` (the policy, signature and any other literal form fields) followed by
+/// `body` as the form's final `file` part, exactly as a browser's `<form>` submission would
+/// lay them out.
+pub(crate) async fn post_object(http_client: &reqwest::Client, url: String, fields: HashMap<String, String>, key: String, body: Bytes) -> Result<PostObjectResponse> {
+    let mut form = Form::new();
+    for (name, value) in fields {
+        form = form.text(name, value);
+    }
+    // "file" must be the last part: OSS treats every part after it as part of the object
+    // body rather than a policy field.
+    form = form.part("file", Part::stream(body).file_name(key));
+
+    let resp = http_client.post(url).multipart(form).send().await?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(process_response_error(resp).await?);
+    }
+
+    let etag = resp
+        .headers()
+        .get(http::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(|etag| etag.trim_matches('"').to_string());
+    let hash_crc64ecma = resp
+        .headers()
+        .get("x-oss-hash-crc64ecma")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    Ok(PostObjectResponse { etag, hash_crc64ecma })
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use serde_json::Value;
+
+/// Builds the policy document (and literal form fields) behind
+/// [`Client::presign_post`](crate::Client::presign_post), so a browser can upload directly
+/// to OSS via an HTML `multipart/form-data` form without proxying the bytes through this
+/// service.
+///
+/// `eq`/[`key`](Self::key)/[`content_type`](Self::content_type) constrain a field to an
+/// exact value known up front, so the value is both signed *and* echoed back as a literal
+/// form field. `starts_with`/[`key_prefix`](Self::key_prefix) only constrain the field the
+/// browser submits, since the exact value (e.g. the picked file's name) isn't known yet.
+#[derive(Debug, Clone, Default)]
+pub struct PostPolicyBuilder {
+    pub(crate) conditions: Vec<Value>,
+    pub(crate) fields: HashMap<String, String>,
+}
+
+impl PostPolicyBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the uploaded object's key to equal exactly `key`.
+    pub fn key(self, key: impl Into<String>) -> Self {
+        self.eq("key", key)
+    }
+
+    /// Require the uploaded object's key to start with `prefix`, e.g. to scope an upload
+    /// to a user- or session-specific folder without fixing the whole key up front.
+    pub fn key_prefix(self, prefix: impl Into<String>) -> Self {
+        self.starts_with("key", prefix)
+    }
+
+    /// Require the uploaded `Content-Type` to equal exactly `content_type`.
+    pub fn content_type(self, content_type: impl Into<String>) -> Self {
+        self.eq("content-type", content_type)
+    }
+
+    /// Require the uploaded body to be between `min` and `max` bytes, inclusive.
+    pub fn content_length_range(mut self, min: u64, max: u64) -> Self {
+        self.conditions
+            .push(json!(["content-length-range", min, max]));
+        self
+    }
+
+    /// Require form field `field` to equal exactly `value`, which is also included as a
+    /// literal field in the returned [`PresignedPost::fields`].
+    pub fn eq(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        let field = field.into();
+        let value = value.into();
+        self.conditions
+            .push(json!({ field.clone(): value.clone() }));
+        self.fields.insert(field, value);
+        self
+    }
+
+    /// Require form field `field` to start with `prefix`. Unlike [`eq`](Self::eq), the
+    /// actual value isn't known up front, so it isn't added to [`PresignedPost::fields`].
+    pub fn starts_with(mut self, field: impl Into<String>, prefix: impl Into<String>) -> Self {
+        let field = field.into();
+        self.conditions
+            .push(json!(["starts-with", format!("${field}"), prefix.into()]));
+        self
+    }
+
+    /// Require the uploaded object's storage class to equal exactly `storage_class`
+    /// (e.g. `"Standard"`, `"IA"`, `"Archive"`).
+    pub fn storage_class(self, storage_class: impl Into<String>) -> Self {
+        self.eq("x-oss-storage-class", storage_class)
+    }
+
+    /// Require user-defined metadata field `x-oss-meta-{key}` to equal exactly `value`.
+    pub fn user_meta(self, key: impl AsRef<str>, value: impl Into<String>) -> Self {
+        self.eq(format!("x-oss-meta-{}", key.as_ref()), value)
+    }
+
+    /// Require user-defined metadata field `x-oss-meta-{key}` to start with `prefix`.
+    pub fn user_meta_prefix(self, key: impl AsRef<str>, prefix: impl Into<String>) -> Self {
+        self.starts_with(format!("x-oss-meta-{}", key.as_ref()), prefix)
+    }
+}
+
+/// The fields an HTML form needs to upload directly to OSS, produced by
+/// [`Client::presign_post`](crate::Client::presign_post).
+#[derive(Debug, Clone)]
+pub struct PresignedPost {
+    /// The URL the form's `action` should point at.
+    pub url: String,
+    /// The hidden fields the form must submit alongside the file, which itself must be
+    /// the form's last field.
+    pub fields: HashMap<String, String>,
+}
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::fmt;
 use std::marker::PhantomData;
+use std::pin::Pin;
 
 use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
 use http::header::CONTENT_TYPE;
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
@@ -33,13 +35,126 @@ impl fmt::Display for ErrorResponse {
 
 impl std::error::Error for ErrorResponse {}
 
-async fn process_response_error(resp: reqwest::Response) -> Result<Error> {
+impl ErrorResponse {
+    /// Parse [`Self::code`] into a typed [`OssErrorCode`], so callers can match on specific
+    /// failure reasons (e.g. to retry) instead of string-comparing the raw code by hand.
+    pub fn oss_error_code(&self) -> OssErrorCode {
+        OssErrorCode::from(self.code.as_str())
+    }
+}
+
+/// A parsed OSS error `Code`, as reported in [`ErrorResponse::code`].
+///
+/// OSS adds new error codes faster than this crate can track them, so an unrecognized code
+/// round-trips through [`OssErrorCode::Other`] instead of being rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OssErrorCode {
+    NoSuchKey,
+    NoSuchBucket,
+    NoSuchUpload,
+    AccessDenied,
+    InvalidAccessKeyId,
+    SignatureDoesNotMatch,
+    RequestTimeTooSkewed,
+    SlowDown,
+    InternalError,
+    ServiceUnavailable,
+    EntityTooLarge,
+    InvalidArgument,
+    InvalidObjectName,
+    MethodNotAllowed,
+    Other(String),
+}
+
+impl OssErrorCode {
+    /// Whether an error with this code is worth retrying: OSS throttling, a transient
+    /// server-side fault, or signing clock skew (which the request retry loop corrects
+    /// before the next attempt, so it self-heals instead of failing repeatedly).
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            OssErrorCode::SlowDown
+                | OssErrorCode::InternalError
+                | OssErrorCode::ServiceUnavailable
+                | OssErrorCode::RequestTimeTooSkewed
+        )
+    }
+}
+
+impl From<&str> for OssErrorCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "NoSuchKey" => OssErrorCode::NoSuchKey,
+            "NoSuchBucket" => OssErrorCode::NoSuchBucket,
+            "NoSuchUpload" => OssErrorCode::NoSuchUpload,
+            "AccessDenied" => OssErrorCode::AccessDenied,
+            "InvalidAccessKeyId" => OssErrorCode::InvalidAccessKeyId,
+            "SignatureDoesNotMatch" => OssErrorCode::SignatureDoesNotMatch,
+            "RequestTimeTooSkewed" => OssErrorCode::RequestTimeTooSkewed,
+            "SlowDown" => OssErrorCode::SlowDown,
+            "InternalError" => OssErrorCode::InternalError,
+            "ServiceUnavailable" => OssErrorCode::ServiceUnavailable,
+            "EntityTooLarge" => OssErrorCode::EntityTooLarge,
+            "InvalidArgument" => OssErrorCode::InvalidArgument,
+            "InvalidObjectName" => OssErrorCode::InvalidObjectName,
+            "MethodNotAllowed" => OssErrorCode::MethodNotAllowed,
+            other => OssErrorCode::Other(other.to_string()),
+        }
+    }
+}
+
+impl AsRef<str> for OssErrorCode {
+    fn as_ref(&self) -> &str {
+        match self {
+            OssErrorCode::NoSuchKey => "NoSuchKey",
+            OssErrorCode::NoSuchBucket => "NoSuchBucket",
+            OssErrorCode::NoSuchUpload => "NoSuchUpload",
+            OssErrorCode::AccessDenied => "AccessDenied",
+            OssErrorCode::InvalidAccessKeyId => "InvalidAccessKeyId",
+            OssErrorCode::SignatureDoesNotMatch => "SignatureDoesNotMatch",
+            OssErrorCode::RequestTimeTooSkewed => "RequestTimeTooSkewed",
+            OssErrorCode::SlowDown => "SlowDown",
+            OssErrorCode::InternalError => "InternalError",
+            OssErrorCode::ServiceUnavailable => "ServiceUnavailable",
+            OssErrorCode::EntityTooLarge => "EntityTooLarge",
+            OssErrorCode::InvalidArgument => "InvalidArgument",
+            OssErrorCode::InvalidObjectName => "InvalidObjectName",
+            OssErrorCode::MethodNotAllowed => "MethodNotAllowed",
+            OssErrorCode::Other(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for OssErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+pub(crate) async fn process_response_error(resp: reqwest::Response) -> Result<Error> {
     let status = resp.status();
     let text = resp.text().await?;
+    error_from_text(status, &text)
+}
+
+/// Build the [`Error::ApiError`] a failed response's already-read body describes. Split
+/// out of [`process_response_error`] so the retry loop can classify a response's OSS error
+/// code (e.g. to detect `RequestTimeTooSkewed`) from text it had to read early, without
+/// reading the body twice.
+pub(crate) fn error_from_text(status: http::StatusCode, text: &str) -> Result<Error> {
+    // Conditional-request outcomes: OSS reports these with no XML body, so surface them as
+    // their own variants instead of a body-less `Error::ApiError`.
+    match status {
+        http::StatusCode::NOT_MODIFIED => return Ok(Error::NotModified),
+        http::StatusCode::PRECONDITION_FAILED => return Ok(Error::PreconditionFailed),
+        _ => {},
+    }
+
     let error = if text.trim().is_empty() {
         None
     } else {
-        Some(Box::new(quick_xml::de::from_str::<ErrorResponse>(&text)?))
+        Some(Box::new(quick_xml::de::from_str::<ErrorResponse>(text)?))
     };
     Ok(Error::ApiError {
         status_code: status,
@@ -78,6 +193,93 @@ impl ResponseProcessor for BinaryResponseProcessor {
     }
 }
 
+/// Like [`BinaryResponseProcessor`], but never buffers the body into memory: the success
+/// path hands back the raw chunk stream so a caller can pipe a multi-gigabyte object
+/// straight to disk instead of holding it all in RAM at once. The error path still needs
+/// the body to deserialize an [`ErrorResponse`](crate::response::ErrorResponse), so only
+/// `process_response_error` reads it eagerly.
+pub(crate) struct StreamResponseProcessor;
+
+impl ResponseProcessor for StreamResponseProcessor {
+    type Output = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
+
+    async fn from_response(resp: reqwest::Response) -> Result<Self::Output> {
+        let status = resp.status();
+        if status.is_success() {
+            Ok(Box::pin(resp.bytes_stream().map_err(Error::from)))
+        } else {
+            Err(process_response_error(resp).await?)
+        }
+    }
+}
+
+/// Like [`StreamResponseProcessor`], but also deserializes the response headers into `T`
+/// (e.g. `Content-Range`/`Content-Length` for a ranged download) so a caller can inspect
+/// them without buffering the body to do so.
+pub(crate) struct StreamHeaderResponseProcessor<T>(PhantomData<T>);
+
+impl<T> ResponseProcessor for StreamHeaderResponseProcessor<T>
+where
+    T: DeserializeOwned,
+{
+    type Output = (Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>, T);
+
+    async fn from_response(resp: reqwest::Response) -> Result<Self::Output> {
+        let status = resp.status();
+        if status.is_success() {
+            let headers = resp.headers();
+            let mut map = HashMap::with_capacity(headers.len());
+            for (key, value) in headers.iter() {
+                // Same rationale as `BinaryHeaderResponseProcessor`: skip headers that
+                // don't round-trip through `to_str`, rather than failing the download.
+                if let Ok(value) = value.to_str() {
+                    map.insert(key.as_str().to_string(), value.to_string());
+                }
+            }
+            let value = serde_json::to_value(map)?;
+            let headers = serde_json::from_value(value)?;
+            Ok((Box::pin(resp.bytes_stream().map_err(Error::from)), headers))
+        } else {
+            Err(process_response_error(resp).await?)
+        }
+    }
+}
+
+/// Like [`BinaryResponseProcessor`], but also deserializes the response headers into `T`
+/// so a caller can inspect them (e.g. to verify `x-oss-hash-crc64ecma`) without a second
+/// round trip.
+pub(crate) struct BinaryHeaderResponseProcessor<T>(PhantomData<T>);
+
+impl<T> ResponseProcessor for BinaryHeaderResponseProcessor<T>
+where
+    T: DeserializeOwned,
+{
+    type Output = (Bytes, T);
+
+    async fn from_response(resp: reqwest::Response) -> Result<Self::Output> {
+        let status = resp.status();
+        if status.is_success() {
+            let headers = resp.headers();
+            let mut map = HashMap::with_capacity(headers.len());
+            for (key, value) in headers.iter() {
+                // Unlike `HeaderResponseProcessor`, the caller only wants a handful of
+                // known-ASCII headers (e.g. `x-oss-hash-crc64ecma`) out of this one, so an
+                // unparsable header value (e.g. a non-ASCII `Content-Disposition`) is
+                // skipped rather than failing the whole (otherwise header-agnostic) download.
+                if let Ok(value) = value.to_str() {
+                    map.insert(key.as_str().to_string(), value.to_string());
+                }
+            }
+            let value = serde_json::to_value(map)?;
+            let headers = serde_json::from_value(value)?;
+            let bytes = resp.bytes().await?;
+            Ok((bytes, headers))
+        } else {
+            Err(process_response_error(resp).await?)
+        }
+    }
+}
+
 pub(crate) struct HeaderResponseProcessor<T>(PhantomData<T>);
 
 impl<T> ResponseProcessor for HeaderResponseProcessor<T>
@@ -131,3 +333,51 @@ where
         }
     }
 }
+
+/// Like [`BodyResponseProcessor`], but also deserializes the response headers into `H`
+/// (e.g. `x-oss-hash-crc64ecma` on a copy response, which OSS reports as a header even
+/// though the rest of the result comes back as an XML body).
+pub(crate) struct BodyHeaderResponseProcessor<T, H>(PhantomData<(T, H)>);
+
+impl<T, H> ResponseProcessor for BodyHeaderResponseProcessor<T, H>
+where
+    T: DeserializeOwned,
+    H: DeserializeOwned,
+{
+    type Output = (T, H);
+
+    async fn from_response(resp: reqwest::Response) -> Result<Self::Output> {
+        let status = resp.status();
+        if status.is_success() {
+            let headers = resp.headers();
+            let mut map = HashMap::with_capacity(headers.len());
+            for (key, value) in headers.iter() {
+                // Same rationale as `BinaryHeaderResponseProcessor`: skip headers that
+                // don't round-trip through `to_str`, rather than failing the request.
+                if let Ok(value) = value.to_str() {
+                    map.insert(key.as_str().to_string(), value.to_string());
+                }
+            }
+            let value = serde_json::to_value(map)?;
+            let headers = serde_json::from_value(value)?;
+
+            let content_type = resp
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("application/xml");
+            let body = match content_type {
+                "application/xml" => {
+                    let text = resp.text().await?;
+                    quick_xml::de::from_str(&text)?
+                },
+                "application/json" => resp.json::<T>().await?,
+                _ => return Err(Error::InvalidContentType(content_type.to_string())),
+            };
+
+            Ok((body, headers))
+        } else {
+            Err(process_response_error(resp).await?)
+        }
+    }
+}
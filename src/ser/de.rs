@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+use std::{fmt, str};
+
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, IntoDeserializer};
+
+use super::Error;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Custom(format!("{msg}").into())
+    }
+}
+
+#[inline]
+pub fn from_str<'de, T>(input: &'de str) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    T::deserialize(Deserializer::from_str(input)?)
+}
+
+#[inline]
+pub fn from_slice<'de, T>(input: &'de [u8]) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    T::deserialize(Deserializer::from_slice(input)?)
+}
+
+/// An intermediate tree grouping the flattened `key=value&...` pairs this crate emits back
+/// into the nested shape [`super::flatten`] flattened out of: dotted path segments
+/// (`inner2.l1`) become nested maps, and purely numeric segments (`inner3.1`) become
+/// 1-based sequence entries.
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf(Option<String>),
+    Branch(BTreeMap<String, Node>),
+}
+
+impl Node {
+    fn into_leaf(self) -> Result<String> {
+        match self {
+            Node::Leaf(value) => Ok(value.unwrap_or_default()),
+            Node::Branch(_) => Err(Error::Custom("expected a scalar value, found a nested path".into())),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Node {
+    type Deserializer = Deserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        Deserializer { node: self }
+    }
+}
+
+fn parse_tree(input: &str) -> Result<BTreeMap<String, Node>> {
+    let mut root = BTreeMap::new();
+    if input.is_empty() {
+        return Ok(root);
+    }
+
+    for pair in input.split('&') {
+        let (key, value) = match pair.split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (pair, None),
+        };
+
+        let key = percent_decode(key)?;
+        let value = value.map(percent_decode).transpose()?;
+
+        let segments: Vec<&str> = key.split('.').collect();
+        insert(&mut root, &segments, value);
+    }
+
+    Ok(root)
+}
+
+fn percent_decode(input: &str) -> Result<String> {
+    use percent_encoding::percent_decode_str;
+
+    Ok(percent_decode_str(input).decode_utf8()?.into_owned())
+}
+
+fn insert(branch: &mut BTreeMap<String, Node>, segments: &[&str], value: Option<String>) {
+    let Some((first, rest)) = segments.split_first() else {
+        return;
+    };
+
+    if rest.is_empty() {
+        branch.insert((*first).to_string(), Node::Leaf(value));
+        return;
+    }
+
+    let child = branch.entry((*first).to_string()).or_insert_with(|| Node::Branch(BTreeMap::new()));
+    if let Node::Branch(child) = child {
+        insert(child, rest, value);
+    }
+}
+
+/// Reverses [`to_string`](super::to_string)/[`to_vec`](super::to_vec): parses the
+/// `key=value&key2=value2` form back into a struct or map via `from_str`/`from_slice`.
+pub struct Deserializer {
+    node: Node,
+}
+
+impl Deserializer {
+    pub fn from_str(input: &str) -> Result<Self> {
+        Ok(Self { node: Node::Branch(parse_tree(input)?) })
+    }
+
+    pub fn from_slice(input: &[u8]) -> Result<Self> {
+        Self::from_str(str::from_utf8(input)?)
+    }
+
+    fn parse_leaf<T>(self) -> Result<T>
+    where
+        T: str::FromStr,
+        T::Err: fmt::Display,
+    {
+        self.node.into_leaf()?.parse().map_err(|err: T::Err| Error::Custom(format!("{err}").into()))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.node {
+            Node::Leaf(value) => visitor.visit_string(value.unwrap_or_default()),
+            Node::Branch(branch) => visitor.visit_map(MapDeserializer::new(branch.into_iter())),
+        }
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(self.parse_leaf()?)
+    }
+
+    fn deserialize_i8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i8(self.parse_leaf()?)
+    }
+
+    fn deserialize_i16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i16(self.parse_leaf()?)
+    }
+
+    fn deserialize_i32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(self.parse_leaf()?)
+    }
+
+    fn deserialize_i64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.parse_leaf()?)
+    }
+
+    fn deserialize_i128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i128(self.parse_leaf()?)
+    }
+
+    fn deserialize_u8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(self.parse_leaf()?)
+    }
+
+    fn deserialize_u16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u16(self.parse_leaf()?)
+    }
+
+    fn deserialize_u32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.parse_leaf()?)
+    }
+
+    fn deserialize_u64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.parse_leaf()?)
+    }
+
+    fn deserialize_u128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u128(self.parse_leaf()?)
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(self.parse_leaf()?)
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(self.parse_leaf()?)
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = self.node.into_leaf()?;
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(Error::Custom(format!("expected a single character, found {value:?}").into())),
+        }
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.node.into_leaf()?)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(self.node.into_leaf()?.into_bytes())
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        // A field is only ever present here when its value was `Some(..)` (the serializer
+        // drops `None` fields entirely), so reaching this deserializer at all means `Some`.
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.node {
+            Node::Branch(branch) => {
+                let mut entries: Vec<(usize, Node)> = branch
+                    .into_iter()
+                    .map(|(key, node)| {
+                        key.parse::<usize>()
+                            .map(|index| (index, node))
+                            .map_err(|_| Error::Custom(format!("non-numeric sequence index: {key}").into()))
+                    })
+                    .collect::<Result<_>>()?;
+                entries.sort_by_key(|(index, _)| *index);
+                visitor.visit_seq(SeqDeserializer::new(entries.into_iter().map(|(_, node)| node)))
+            },
+            Node::Leaf(_) => Err(Error::Custom("expected a sequence, found a scalar value".into())),
+        }
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.node {
+            Node::Branch(branch) => visitor.visit_map(MapDeserializer::new(branch.into_iter())),
+            Node::Leaf(_) => Err(Error::Custom("expected a map, found a scalar value".into())),
+        }
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value> {
+        Err(Error::UnsupportedType("enum"))
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_any(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[test]
+    fn test_scalar_fields() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct TestStruct {
+            a: u32,
+            d: u32,
+            b: Option<u32>,
+            c: Option<String>,
+        }
+
+        let parsed: TestStruct = from_str("a=1&d=2").unwrap();
+        assert_eq!(parsed, TestStruct { a: 1, d: 2, b: None, c: None });
+
+        let parsed2: TestStruct = from_str("a=1&b=42&d=2").unwrap();
+        assert_eq!(parsed2, TestStruct { a: 1, d: 2, b: Some(42), c: None });
+    }
+
+    #[test]
+    fn test_nested_struct_and_seq() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct TestLeafStruct {
+            l1: String,
+            l2: u32,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct TestInnerStruct {
+            inner1: String,
+            inner2: TestLeafStruct,
+            inner3: [u32; 3],
+        }
+
+        let parsed: TestInnerStruct =
+            from_str("inner1=test&inner2.l1=aaa&inner2.l2=3&inner3.1=3&inner3.2=4&inner3.3=5").unwrap();
+        assert_eq!(
+            parsed,
+            TestInnerStruct {
+                inner1: "test".to_string(),
+                inner2: TestLeafStruct { l1: "aaa".to_string(), l2: 3 },
+                inner3: [3, 4, 5],
+            }
+        );
+    }
+
+    #[test]
+    fn test_percent_decoding() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct TestStruct {
+            v: String,
+        }
+
+        let parsed: TestStruct = from_str("v=%3C_._%3E~01abc_-.%2B").unwrap();
+        assert_eq!(parsed, TestStruct { v: "<_._>~01abc_-.+".to_string() });
+    }
+}
@@ -0,0 +1,49 @@
+//! `#[serde(with = "...")]` helper for binary fields serialized through
+//! [`super::to_vec_with`]/[`super::to_writer_with`]'s base64 mode.
+//!
+//! Serializing defers to `serialize_bytes`, so it only actually base64-encodes when the
+//! active [`FlattenSerializer`](super::FlattenSerializer) was built with
+//! [`with_base64(true)`](super::FlattenSerializer::with_base64) — plain `to_vec`/`to_writer`
+//! still require valid UTF-8, unchanged. Deserializing always base64-decodes the leaf string,
+//! since [`Deserializer`](super::de::Deserializer) has no way to tell whether a given leaf was
+//! base64-encoded; pair this helper only with fields that were serialized in base64 mode.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Deserializer, Serializer, de};
+
+pub fn serialize<S>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_bytes(value)
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let encoded = String::deserialize(deserializer)?;
+    STANDARD.decode(encoded.as_bytes()).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::super::{de, to_vec_with};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestStruct {
+        #[serde(with = "super")]
+        payload: Vec<u8>,
+    }
+
+    #[test]
+    fn test_roundtrip_non_utf8_bytes() {
+        let value = TestStruct { payload: vec![0xff, 0x00, 0x10, 0x7f] };
+        let encoded = to_vec_with(&value, true).unwrap();
+        let decoded: TestStruct = de::from_slice(&encoded).unwrap();
+        assert_eq!(value, decoded);
+    }
+}
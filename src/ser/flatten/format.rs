@@ -0,0 +1,97 @@
+use std::io;
+
+use super::super::Error;
+
+/// Controls how a [`super::FlattenSerializer`] frames the flattened dotted-key output:
+/// whether entries are wrapped in an object, how a key is separated from its value, and
+/// how two entries are separated from each other.
+///
+/// The default methods reproduce the original `{"k":"v","k2":"v2"}` JSON-object shape,
+/// so a custom `Formatter` only needs to override what actually differs. The
+/// flattening/dotted-key logic, alphabetical sort and percent-encoding of values stay
+/// shared across every `Formatter`.
+pub trait Formatter {
+    /// Write the opening delimiter of the top-level object. Only called when the value
+    /// being serialized is itself a seq/tuple/map/struct (i.e. at `top_level`).
+    fn write_begin_object<W: ?Sized + io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(b"{")?;
+        Ok(())
+    }
+
+    /// Write the closing delimiter of the top-level object.
+    fn write_end_object<W: ?Sized + io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(b"}")?;
+        Ok(())
+    }
+
+    /// Write a flattened, dotted key. Not called for keys with no value.
+    fn write_key<W: ?Sized + io::Write>(&self, writer: &mut W, key: &str) -> Result<(), Error> {
+        writer.write_all(b"\"")?;
+        writer.write_all(key.as_bytes())?;
+        writer.write_all(b"\"")?;
+        Ok(())
+    }
+
+    /// Write the separator between a key and its value. Called right after `write_key`.
+    fn write_key_value_separator<W: ?Sized + io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(b":")?;
+        Ok(())
+    }
+
+    /// Write the separator between two entries. Called once before every entry after
+    /// the first.
+    fn write_entry_separator<W: ?Sized + io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(b",")?;
+        Ok(())
+    }
+
+    /// Write an already percent-encoded scalar value.
+    fn write_string_value<W: ?Sized + io::Write>(&self, writer: &mut W, value: &str) -> Result<(), Error> {
+        writer.write_all(b"\"")?;
+        writer.write_all(value.as_bytes())?;
+        writer.write_all(b"\"")?;
+        Ok(())
+    }
+}
+
+/// The default [`Formatter`]: the original `{"k":"v","k2":"v2"}` JSON-object shape.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {}
+
+/// A [`Formatter`] producing `application/x-www-form-urlencoded` output (`k=v&k2=v2`)
+/// instead of a JSON object, for OSS tagging headers and query strings that want the
+/// same flattened, dotted-key fields as a plain query string rather than a JSON body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormUrlEncodedFormatter;
+
+impl Formatter for FormUrlEncodedFormatter {
+    fn write_begin_object<W: ?Sized + io::Write>(&self, _writer: &mut W) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn write_end_object<W: ?Sized + io::Write>(&self, _writer: &mut W) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn write_key<W: ?Sized + io::Write>(&self, writer: &mut W, key: &str) -> Result<(), Error> {
+        writer.write_all(key.as_bytes())?;
+        Ok(())
+    }
+
+    fn write_key_value_separator<W: ?Sized + io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(b"=")?;
+        Ok(())
+    }
+
+    fn write_entry_separator<W: ?Sized + io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(b"&")?;
+        Ok(())
+    }
+
+    fn write_string_value<W: ?Sized + io::Write>(&self, writer: &mut W, value: &str) -> Result<(), Error> {
+        writer.write_all(value.as_bytes())?;
+        Ok(())
+    }
+}
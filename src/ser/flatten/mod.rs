@@ -1,18 +1,27 @@
+pub mod base64_bytes;
+pub mod de;
+mod format;
+mod key;
+
 use std::borrow::Cow;
 use std::{io, mem, str};
 
 use serde::{Serialize, ser};
 
+pub use self::format::{Formatter, FormUrlEncodedFormatter, JsonFormatter};
+use self::key::KeySerializer;
 use super::{Error, percent_encode};
 
-pub struct FlattenSerializer<'a, W> {
+pub struct FlattenSerializer<'a, W, F = JsonFormatter> {
     writer: &'a mut W,
 
     key: Option<Cow<'a, str>>,
     top_level: bool,
+    base64: bool,
+    formatter: F,
 }
 
-impl<'a, W> FlattenSerializer<'a, W>
+impl<'a, W> FlattenSerializer<'a, W, JsonFormatter>
 where
     W: io::Write,
 {
@@ -21,6 +30,40 @@ where
             writer,
             key: None,
             top_level: true,
+            base64: false,
+            formatter: JsonFormatter,
+        }
+    }
+}
+
+impl<'a, W, F> FlattenSerializer<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter + Copy,
+{
+    /// When set, `serialize_bytes` base64-encodes its payload instead of requiring valid
+    /// UTF-8, so binary fields (checksums, raw tag blobs) can be serialized; the base64
+    /// output is then percent-encoded as usual, since it can itself contain `+`, `/` and
+    /// `=`. Use the [`base64_bytes`] `serde(with = ...)` helper on such fields so they
+    /// round-trip cleanly against [`de`].
+    pub(crate) fn with_base64(mut self, base64: bool) -> Self {
+        self.base64 = base64;
+        self
+    }
+
+    /// Swaps in a custom [`Formatter`], e.g. [`FormUrlEncodedFormatter`] to emit
+    /// `k=v&k2=v2` instead of the default JSON-object shape. The flattening, sorting and
+    /// percent-encoding of values is unaffected by the choice of formatter.
+    pub(crate) fn with_formatter<F2>(self, formatter: F2) -> FlattenSerializer<'a, W, F2>
+    where
+        F2: Formatter + Copy,
+    {
+        FlattenSerializer {
+            writer: self.writer,
+            key: self.key,
+            top_level: self.top_level,
+            base64: self.base64,
+            formatter,
         }
     }
 
@@ -44,28 +87,28 @@ where
 
     fn serialize_key(&mut self) -> Result<(), Error> {
         if let Some(key) = &self.key {
-            self.writer.write_all(b"\"")?;
-            self.writer.write_all(key.as_bytes())?;
-            self.writer.write_all(b"\":")?;
+            self.formatter.write_key(self.writer, key)?;
+            self.formatter.write_key_value_separator(self.writer)?;
         }
         Ok(())
     }
 }
 
-impl<'a, W> ser::Serializer for &'a mut FlattenSerializer<'a, W>
+impl<'a, W, F> ser::Serializer for &'a mut FlattenSerializer<'a, W, F>
 where
     W: io::Write,
+    F: Formatter + Copy,
 {
     type Ok = ();
     type Error = Error;
 
-    type SerializeSeq = FlattenSeqSerializer<'a, W>;
-    type SerializeTuple = FlattenTupleSerializer<'a, W>;
-    type SerializeMap = FlattenMapSerializer<'a, W>;
-    type SerializeStruct = FlattenStructSerializer<'a, W>;
+    type SerializeSeq = FlattenSeqSerializer<'a, W, F>;
+    type SerializeTuple = FlattenTupleSerializer<'a, W, F>;
+    type SerializeMap = FlattenMapSerializer<'a, W, F>;
+    type SerializeStruct = FlattenStructSerializer<'a, W, F>;
     type SerializeTupleStruct = ser::Impossible<Self::Ok, Self::Error>;
-    type SerializeTupleVariant = ser::Impossible<Self::Ok, Self::Error>;
-    type SerializeStructVariant = ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = FlattenTupleSerializer<'a, W, F>;
+    type SerializeStructVariant = FlattenStructSerializer<'a, W, F>;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
         self.serialize_str(if v { "true" } else { "false" })
@@ -123,11 +166,9 @@ where
         let percent_encoding_v = percent_encode(v);
         self.serialize_key()?;
         if !self.top_level {
-            self.writer.write_all(b"\"")?;
-        }
-        self.writer.write_all(percent_encoding_v.as_bytes())?;
-        if !self.top_level {
-            self.writer.write_all(b"\"")?;
+            self.formatter.write_string_value(self.writer, &percent_encoding_v)?;
+        } else {
+            self.writer.write_all(percent_encoding_v.as_bytes())?;
         }
         Ok(())
     }
@@ -139,7 +180,12 @@ where
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        self.serialize_str(str::from_utf8(v)?)
+        if self.base64 {
+            use base64::Engine;
+            self.serialize_str(&base64::engine::general_purpose::STANDARD.encode(v))
+        } else {
+            self.serialize_str(str::from_utf8(v)?)
+        }
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
@@ -171,7 +217,7 @@ where
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
         self.serialize_key()?;
         if self.key.is_some() {
-            self.writer.write_all(b"\"\"")?;
+            self.formatter.write_string_value(self.writer, "")?;
         }
         Ok(())
     }
@@ -183,7 +229,7 @@ where
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
         if self.top_level {
-            self.writer.write_all(b"{")?;
+            self.formatter.write_begin_object(self.writer)?;
         }
         Ok(FlattenSeqSerializer {
             writer: self.writer,
@@ -191,12 +237,14 @@ where
             first: true,
             index: 0,
             top_level: self.top_level,
+            base64: self.base64,
+            formatter: self.formatter,
         })
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
         if self.top_level {
-            self.writer.write_all(b"{")?;
+            self.formatter.write_begin_object(self.writer)?;
         }
         Ok(FlattenTupleSerializer {
             writer: self.writer,
@@ -204,6 +252,8 @@ where
             first: true,
             index: 0,
             top_level: self.top_level,
+            base64: self.base64,
+            formatter: self.formatter,
         })
     }
 
@@ -213,6 +263,8 @@ where
             pre_key: self.key.clone(),
             entries: Vec::new(),
             top_level: self.top_level,
+            base64: self.base64,
+            formatter: self.formatter,
         })
     }
 
@@ -226,6 +278,8 @@ where
             pre_key: self.key.clone(),
             entries: Vec::new(),
             top_level: self.top_level,
+            base64: self.base64,
+            formatter: self.formatter,
         })
     }
 
@@ -233,10 +287,23 @@ where
         self,
         _name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
-        _value: &T,
+        variant: &'static str,
+        value: &T,
     ) -> Result<Self::Ok, Self::Error> {
-        Err(Error::UnsupportedType("newtype variant"))
+        let new_key: Cow<'_, str> = if let Some(pre_key) = &self.key {
+            format!("{pre_key}.{variant}").into()
+        } else {
+            variant.into()
+        };
+
+        let mut value_serializer = FlattenSerializer {
+            writer: self.writer,
+            key: Some(new_key),
+            top_level: self.top_level,
+            base64: self.base64,
+            formatter: self.formatter,
+        };
+        value.serialize(&mut value_serializer)
     }
 
     fn serialize_tuple_struct(
@@ -251,35 +318,68 @@ where
         self,
         _name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        Err(Error::UnsupportedType("tuple variant"))
+        let new_key: Cow<'_, str> = if let Some(pre_key) = &self.key {
+            format!("{pre_key}.{variant}").into()
+        } else {
+            variant.into()
+        };
+
+        if self.top_level {
+            self.formatter.write_begin_object(self.writer)?;
+        }
+        Ok(FlattenTupleSerializer {
+            writer: self.writer,
+            pre_key: Some(new_key),
+            first: true,
+            index: 0,
+            top_level: self.top_level,
+            base64: self.base64,
+            formatter: self.formatter,
+        })
     }
 
     fn serialize_struct_variant(
         self,
         _name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Err(Error::UnsupportedType("struct variant"))
+        let new_key: Cow<'_, str> = if let Some(pre_key) = &self.key {
+            format!("{pre_key}.{variant}").into()
+        } else {
+            variant.into()
+        };
+
+        Ok(FlattenStructSerializer {
+            writer: self.writer,
+            pre_key: Some(new_key),
+            entries: Vec::new(),
+            top_level: self.top_level,
+            base64: self.base64,
+            formatter: self.formatter,
+        })
     }
 }
 
-pub struct FlattenSeqSerializer<'a, W> {
+pub struct FlattenSeqSerializer<'a, W, F = JsonFormatter> {
     writer: &'a mut W,
 
     first: bool,
     index: usize,
     top_level: bool,
     pre_key: Option<Cow<'a, str>>,
+    base64: bool,
+    formatter: F,
 }
 
-impl<W> ser::SerializeSeq for FlattenSeqSerializer<'_, W>
+impl<W, F> ser::SerializeSeq for FlattenSeqSerializer<'_, W, F>
 where
     W: io::Write,
+    F: Formatter + Copy,
 {
     type Ok = ();
     type Error = Error;
@@ -287,7 +387,7 @@ where
     fn serialize_element<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
         let first = mem::replace(&mut self.first, false);
         if !first {
-            self.writer.write_all(b",")?;
+            self.formatter.write_entry_separator(self.writer)?;
         }
 
         self.index += 1;
@@ -303,30 +403,35 @@ where
             writer: self.writer,
             key: Some(new_key),
             top_level: false,
+            base64: self.base64,
+            formatter: self.formatter,
         };
         value.serialize(&mut value_serializer)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
         if self.top_level {
-            self.writer.write_all(b"}")?;
+            self.formatter.write_end_object(self.writer)?;
         }
         Ok(())
     }
 }
 
-pub struct FlattenTupleSerializer<'a, W> {
+pub struct FlattenTupleSerializer<'a, W, F = JsonFormatter> {
     writer: &'a mut W,
 
     pre_key: Option<Cow<'a, str>>,
     first: bool,
     index: usize,
     top_level: bool,
+    base64: bool,
+    formatter: F,
 }
 
-impl<W> ser::SerializeTuple for FlattenTupleSerializer<'_, W>
+impl<W, F> ser::SerializeTuple for FlattenTupleSerializer<'_, W, F>
 where
     W: io::Write,
+    F: Formatter + Copy,
 {
     type Ok = ();
     type Error = Error;
@@ -334,7 +439,7 @@ where
     fn serialize_element<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
         let first = mem::replace(&mut self.first, false);
         if !first {
-            self.writer.write_all(b",")?;
+            self.formatter.write_entry_separator(self.writer)?;
         }
 
         self.index += 1;
@@ -350,29 +455,34 @@ where
             writer: self.writer,
             key: Some(new_key),
             top_level: false,
+            base64: self.base64,
+            formatter: self.formatter,
         };
         value.serialize(&mut value_serializer)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
         if self.top_level {
-            self.writer.write_all(b"}")?;
+            self.formatter.write_end_object(self.writer)?;
         }
         Ok(())
     }
 }
 
-pub struct FlattenMapSerializer<'a, W> {
+pub struct FlattenMapSerializer<'a, W, F = JsonFormatter> {
     writer: &'a mut W,
 
     pre_key: Option<Cow<'a, str>>,
     entries: Vec<(String, Vec<u8>)>,
     top_level: bool,
+    base64: bool,
+    formatter: F,
 }
 
-impl<W> ser::SerializeMap for FlattenMapSerializer<'_, W>
+impl<W, F> ser::SerializeMap for FlattenMapSerializer<'_, W, F>
 where
     W: io::Write,
+    F: Formatter + Copy,
 {
     type Ok = ();
     type Error = Error;
@@ -382,15 +492,7 @@ where
         key: &K,
         value: &V,
     ) -> Result<(), Self::Error> {
-        // Collect keys for sorting - we need to serialize to string
-        let mut key_writer = Vec::new();
-        let mut key_serializer = FlattenSerializer {
-            writer: &mut key_writer,
-            key: None,
-            top_level: false,
-        };
-        key.serialize(&mut key_serializer)?;
-        let key_str = String::from_utf8(key_writer).map_err(|_| Error::Custom("invalid UTF-8 key".into()))?;
+        let key_str = key.serialize(KeySerializer)?;
 
         let new_key: Cow<'_, str> = if let Some(pre_key) = &self.pre_key {
             format!("{pre_key}.{key_str}").into()
@@ -403,6 +505,8 @@ where
             writer: &mut value_writer,
             key: Some(new_key),
             top_level: false,
+            base64: self.base64,
+            formatter: self.formatter,
         };
         value.serialize(&mut value_serializer)?;
         self.entries.push((key_str, value_writer));
@@ -421,7 +525,7 @@ where
 
     fn end(mut self) -> Result<Self::Ok, Self::Error> {
         if self.top_level {
-            self.writer.write_all(b"{")?;
+            self.formatter.write_begin_object(self.writer)?;
         }
 
         self.entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -429,28 +533,31 @@ where
         for (i, (_, value)) in self.entries.iter().enumerate() {
             self.writer.write_all(value)?;
             if i < self.entries.len() - 1 {
-                self.writer.write_all(b",")?;
+                self.formatter.write_entry_separator(self.writer)?;
             }
         }
 
         if self.top_level {
-            self.writer.write_all(b"}")?;
+            self.formatter.write_end_object(self.writer)?;
         }
         Ok(())
     }
 }
 
-pub struct FlattenStructSerializer<'a, W> {
+pub struct FlattenStructSerializer<'a, W, F = JsonFormatter> {
     writer: &'a mut W,
 
     pre_key: Option<Cow<'a, str>>,
     entries: Vec<(String, Vec<u8>)>,
     top_level: bool,
+    base64: bool,
+    formatter: F,
 }
 
-impl<W> ser::SerializeStruct for FlattenStructSerializer<'_, W>
+impl<W, F> ser::SerializeStruct for FlattenStructSerializer<'_, W, F>
 where
     W: io::Write,
+    F: Formatter + Copy,
 {
     type Ok = ();
     type Error = Error;
@@ -471,8 +578,9 @@ where
             writer: &mut value_writer,
             key: Some(new_key),
             top_level: false,
+            base64: self.base64,
+            formatter: self.formatter,
         };
-        // Print value_writer as string for debugging
         value.serialize(&mut value_serializer)?;
         self.entries.push((key.to_string(), value_writer));
         Ok(())
@@ -480,7 +588,7 @@ where
 
     fn end(mut self) -> Result<Self::Ok, Self::Error> {
         if self.top_level {
-            self.writer.write_all(b"{")?;
+            self.formatter.write_begin_object(self.writer)?;
         }
 
         self.entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -488,12 +596,12 @@ where
         for (i, (_, value)) in self.entries.iter().enumerate() {
             self.writer.write_all(value)?;
             if i < self.entries.len() - 1 {
-                self.writer.write_all(b",")?;
+                self.formatter.write_entry_separator(self.writer)?;
             }
         }
 
         if self.top_level {
-            self.writer.write_all(b"}")?;
+            self.formatter.write_end_object(self.writer)?;
         }
         Ok(())
     }
@@ -519,6 +627,56 @@ where
     Ok(writer)
 }
 
+/// Like [`to_writer`], but with `base64` forwarded to [`FlattenSerializer::with_base64`] so
+/// `serialize_bytes` can encode non-UTF-8 payloads instead of erroring.
+#[inline]
+pub fn to_writer_with<W, T>(mut writer: W, input: &T, base64: bool) -> Result<(), Error>
+where
+    W: io::Write,
+    T: ?Sized + Serialize,
+{
+    let mut ser = FlattenSerializer::new(&mut writer).with_base64(base64);
+    input.serialize(&mut ser)
+}
+
+/// Like [`to_writer`], but with output framing delegated to a custom [`Formatter`]
+/// (e.g. [`FormUrlEncodedFormatter`]) instead of the default JSON-object shape.
+#[inline]
+pub fn to_writer_with_formatter<W, T, F>(mut writer: W, input: &T, formatter: F) -> Result<(), Error>
+where
+    W: io::Write,
+    T: ?Sized + Serialize,
+    F: Formatter + Copy,
+{
+    let mut ser = FlattenSerializer::new(&mut writer).with_formatter(formatter);
+    input.serialize(&mut ser)
+}
+
+/// Like [`to_vec`], but with output framing delegated to a custom [`Formatter`] (e.g.
+/// [`FormUrlEncodedFormatter`]) instead of the default JSON-object shape.
+#[inline]
+pub fn to_vec_with_formatter<T, F>(input: &T, formatter: F) -> Result<Vec<u8>, Error>
+where
+    T: ?Sized + Serialize,
+    F: Formatter + Copy,
+{
+    let mut writer = Vec::with_capacity(512);
+    to_writer_with_formatter(&mut writer, input, formatter)?;
+    Ok(writer)
+}
+
+/// Like [`to_vec`], but with `base64` forwarded to [`FlattenSerializer::with_base64`] so
+/// `serialize_bytes` can encode non-UTF-8 payloads instead of erroring.
+#[inline]
+pub fn to_vec_with<T>(input: &T, base64: bool) -> Result<Vec<u8>, Error>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = Vec::with_capacity(512);
+    to_writer_with(&mut writer, input, base64)?;
+    Ok(writer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -562,4 +720,93 @@ mod tests {
             br#"{"w.1":"test","w.2":"1","x.inner1":"test","x.inner2.l1":"aaa","x.inner2.l2":"3","x.inner3.1":"3","x.inner3.2":"4","x.inner3.3":"5","y":"2","z":"1"}"#
         );
     }
+
+    #[derive(Serialize)]
+    enum TestMode {
+        Simple,
+        Newtype(u32),
+        Tuple(u32, &'static str),
+        Struct { a: u32, b: &'static str },
+    }
+
+    #[derive(Serialize)]
+    struct TestEnumStruct {
+        id: u32,
+        mode: TestMode,
+    }
+
+    #[test]
+    fn test_flatten_serializer_unit_variant() {
+        let result = to_vec(&TestEnumStruct { id: 1, mode: TestMode::Simple }).unwrap();
+        assert_eq!(result, br#"{"id":"1","mode":"Simple"}"#);
+    }
+
+    #[test]
+    fn test_flatten_serializer_newtype_variant() {
+        let result = to_vec(&TestEnumStruct { id: 1, mode: TestMode::Newtype(42) }).unwrap();
+        assert_eq!(result, br#"{"id":"1","mode.Newtype":"42"}"#);
+    }
+
+    #[test]
+    fn test_flatten_serializer_tuple_variant() {
+        let result = to_vec(&TestEnumStruct { id: 1, mode: TestMode::Tuple(42, "x") }).unwrap();
+        assert_eq!(result, br#"{"id":"1","mode.Tuple.1":"42","mode.Tuple.2":"x"}"#);
+    }
+
+    #[test]
+    fn test_flatten_serializer_struct_variant() {
+        let result = to_vec(&TestEnumStruct { id: 1, mode: TestMode::Struct { a: 1, b: "x" } }).unwrap();
+        assert_eq!(result, br#"{"id":"1","mode.Struct.a":"1","mode.Struct.b":"x"}"#);
+    }
+
+    #[derive(Serialize)]
+    struct TestBytesStruct {
+        payload: serde_bytes_value::Bytes,
+    }
+
+    /// A `&[u8]` newtype whose `Serialize` impl goes through `serialize_bytes` rather than
+    /// `serialize_seq`, matching what `serde_bytes`/`#[serde(with = "base64_bytes")]` fields
+    /// actually produce.
+    mod serde_bytes_value {
+        use serde::{Serialize, Serializer};
+
+        pub struct Bytes(pub &'static [u8]);
+
+        impl Serialize for Bytes {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+    }
+
+    #[test]
+    fn test_flatten_serializer_bytes_rejects_non_utf8_by_default() {
+        let err = to_vec(&TestBytesStruct { payload: serde_bytes_value::Bytes(b"\xff\x00") }).unwrap_err();
+        assert!(matches!(err, Error::Utf8(_)));
+    }
+
+    #[test]
+    fn test_flatten_serializer_bytes_base64_mode() {
+        let result = to_vec_with(&TestBytesStruct { payload: serde_bytes_value::Bytes(b"\xff\x00") }, true).unwrap();
+        assert_eq!(result, br#"{"payload":"%2FwA%3D"}"#);
+    }
+
+    #[test]
+    fn test_flatten_serializer_form_urlencoded_formatter() {
+        let u = TestStruct {
+            z: 1,
+            y: 2,
+            x: TestInnerStruct {
+                inner1: "test",
+                inner2: TestLeafStruct { l1: "aaa", l2: 3 },
+                inner3: [3, 4, 5],
+            },
+            w: ("test", 1),
+        };
+        let result = to_vec_with_formatter(&u, FormUrlEncodedFormatter).unwrap();
+        assert_eq!(
+            result,
+            b"w.1=test&w.2=1&x.inner1=test&x.inner2.l1=aaa&x.inner2.l2=3&x.inner3.1=3&x.inner3.2=4&x.inner3.3=5&y=2&z=1"
+        );
+    }
 }
@@ -1,16 +1,16 @@
-use std::{io, str};
+use std::str;
 
 use serde::ser;
 
-use crate::ser::Error;
+use crate::ser::{Error, Writer};
 
-pub struct KeySerializer<'a, W: io::Write> {
+pub struct KeySerializer<'a, W: Writer> {
     pub(crate) writer: &'a mut W,
 }
 
 impl<W> KeySerializer<'_, W>
 where
-    W: io::Write,
+    W: Writer,
 {
     fn serialize_integer<I>(&mut self, value: I) -> Result<(), Error>
     where
@@ -33,7 +33,7 @@ where
 
 impl<W> ser::Serializer for &mut KeySerializer<'_, W>
 where
-    W: io::Write,
+    W: Writer,
 {
     type Ok = ();
     type Error = Error;
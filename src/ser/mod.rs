@@ -1,14 +1,21 @@
+pub mod de;
 pub mod flatten;
 mod key;
 mod pair;
+mod writer;
 
 use std::borrow::Cow;
-use std::{fmt, io, mem, str};
+#[cfg(feature = "std")]
+use std::io;
+use std::{fmt, mem, str};
 
 use serde::{Serialize, ser};
 
+pub use writer::{SliceWriter, Writer};
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[cfg(feature = "std")]
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
 
@@ -32,6 +39,12 @@ pub enum Error {
 
     #[error("tried to serialize a unit value")]
     Empty,
+
+    #[error("output buffer is full")]
+    BufferFull,
+
+    #[error("map/struct serialization needs to buffer and sort entries, which requires the `alloc` feature")]
+    RequiresAlloc,
 }
 
 impl ser::Error for Error {
@@ -40,18 +53,118 @@ impl ser::Error for Error {
     }
 }
 
-type Result<T, E = Error> = std::result::Result<T, E>;
+type Result<T, E = Error> = core::result::Result<T, E>;
 
 #[inline]
 pub fn to_writer<W, T>(writer: W, input: &T) -> Result<()>
 where
-    W: io::Write,
+    W: Writer,
     T: ?Sized + Serialize,
 {
     let mut ser = Serializer::new(writer);
     input.serialize(&mut ser)
 }
 
+/// Serializes `input` into the caller-supplied `buf`, returning the written prefix.
+///
+/// Unlike [`to_vec`]/[`to_string`], this does not allocate the output buffer itself, so
+/// it is the entry point to reach for on targets without `std::io::Write` (embedded,
+/// kernel code) as long as a global allocator is still available — map and struct fields
+/// are still buffered and sorted on the heap via [`MapSerializer`]/[`StructSerializer`],
+/// which needs the `alloc` feature. Returns [`Error::BufferFull`] if `buf` is too small.
+#[inline]
+pub fn to_slice<'b, T>(buf: &'b mut [u8], input: &T) -> Result<&'b mut [u8]>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = SliceWriter::new(buf);
+    to_writer(&mut writer, input)?;
+    Ok(writer.into_inner())
+}
+
+/// Controls how a [`Serializer`] renders the top-level `key=value&key2=value2` output:
+/// the separators between pairs, the key/value encoding, and the entry ordering.
+///
+/// The default methods reproduce today's behavior (`&`-separated, `=`-delimited,
+/// alphabetically-sorted, unencoded keys/values), so implementing just the methods that
+/// need to change is enough to customize one aspect of the output.
+pub trait Formatter {
+    /// Write the separator between two `key=value` entries. Called once before every
+    /// entry after the first.
+    fn write_pair_separator<W: Writer>(&mut self, writer: &mut W) -> Result<()> {
+        writer.write_all(b"&")?;
+        Ok(())
+    }
+
+    /// Write the separator between a key and its value. Not called for keys with no
+    /// value (e.g. a unit-typed field).
+    fn write_key_value_separator<W: Writer>(&mut self, writer: &mut W) -> Result<()> {
+        writer.write_all(b"=")?;
+        Ok(())
+    }
+
+    /// Encode a key immediately before it is written.
+    fn encode_key<'k>(&self, key: &'k str) -> Cow<'k, str> {
+        Cow::Borrowed(key)
+    }
+
+    /// Encode a value immediately before it is written.
+    fn encode_value<'v>(&self, value: &'v [u8]) -> Cow<'v, [u8]> {
+        Cow::Borrowed(value)
+    }
+
+    /// Order the collected `(key, value)` entries before they're written. The default
+    /// sorts alphabetically by the raw (unencoded) key, matching today's behavior.
+    fn sort_entries(&self, entries: &mut Vec<(String, Option<Vec<u8>>)>) {
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    /// Whether a valueless entry (e.g. a unit-typed field) should still emit the
+    /// key/value separator, producing `key=` instead of a bare `key`. Defaults to
+    /// `false`, matching today's behavior.
+    fn force_key_value_separator(&self) -> bool {
+        false
+    }
+}
+
+/// The default [`Formatter`]: `&`-separated, `=`-delimited, alphabetically-sorted,
+/// unencoded keys/values. This is today's (pre-`Formatter`) behavior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CanonicalFormatter;
+
+impl Formatter for CanonicalFormatter {}
+
+/// A [`Formatter`] producing an RFC 3986 canonical query string, as required by AWS
+/// SigV4-style request signing: keys are percent-encoded with the same unreserved set
+/// `percent_encode` uses for values, entries are sorted by the *encoded* key bytes
+/// (breaking ties by the encoded value bytes), and a valueless entry is written as
+/// `key=` rather than a bare `key` so every parameter has the `key=value` shape.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SigV4Formatter;
+
+impl Formatter for SigV4Formatter {
+    fn encode_key<'k>(&self, key: &'k str) -> Cow<'k, str> {
+        percent_encode(key)
+    }
+
+    fn sort_entries(&self, entries: &mut Vec<(String, Option<Vec<u8>>)>) {
+        entries.sort_by(|a, b| {
+            let a_key = self.encode_key(&a.0);
+            let b_key = self.encode_key(&b.0);
+            a_key.cmp(&b_key).then_with(|| {
+                let a_value = self.encode_value(a.1.as_deref().unwrap_or(&[]));
+                let b_value = self.encode_value(b.1.as_deref().unwrap_or(&[]));
+                a_value.cmp(&b_value)
+            })
+        });
+    }
+
+    fn force_key_value_separator(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "alloc")]
 #[inline]
 pub fn to_vec<T>(input: &T) -> Result<Vec<u8>>
 where
@@ -62,6 +175,7 @@ where
     Ok(writer)
 }
 
+#[cfg(feature = "alloc")]
 #[inline]
 pub fn to_string<T>(input: &T) -> Result<String>
 where
@@ -75,58 +189,121 @@ where
     Ok(string)
 }
 
-pub struct Serializer<W> {
+/// Controls how struct and tuple enum variants are rendered by [`Serializer`].
+///
+/// Mirrors serde_cbor's `enum_as_map` option: in the default [`EnumRepr::Tagged`] mode a
+/// struct variant `E::V { a, b }` renders as `V.a=...&V.b=...` and a tuple variant
+/// `E::V(x, y)` as `V.1=...&V.2=...`, reusing the dotted-path convention nested
+/// structs/tuples already use for values. [`EnumRepr::Untagged`] drops the `V.` prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnumRepr {
+    #[default]
+    Tagged,
+    Untagged,
+}
+
+pub struct Serializer<W, F = CanonicalFormatter> {
     writer: W,
+    formatter: F,
+    enum_repr: EnumRepr,
 }
 
-impl<W> Serializer<W>
+impl<W> Serializer<W, CanonicalFormatter>
 where
-    W: io::Write,
+    W: Writer,
 {
-    /// Creates a new serializer.
+    /// Creates a new serializer using the default [`CanonicalFormatter`].
     #[inline]
     pub fn new(writer: W) -> Self {
-        Serializer { writer }
+        Serializer {
+            writer,
+            formatter: CanonicalFormatter,
+            enum_repr: EnumRepr::default(),
+        }
     }
+}
 
-    /// Unwrap the `Writer` from the `Serializer`.
+impl<W, F> Serializer<W, F>
+where
+    W: Writer,
+    F: Formatter,
+{
+    /// Creates a new serializer using a custom [`Formatter`].
+    #[inline]
+    pub fn with_formatter(writer: W, formatter: F) -> Self {
+        Serializer { writer, formatter, enum_repr: EnumRepr::default() }
+    }
+
+    /// Overrides how struct/tuple enum variants are rendered. Defaults to [`EnumRepr::Tagged`].
+    #[inline]
+    pub fn with_enum_repr(mut self, enum_repr: EnumRepr) -> Self {
+        self.enum_repr = enum_repr;
+        self
+    }
+
+    /// Unwrap the [`Writer`] from the `Serializer`.
     #[inline]
     pub fn into_inner(self) -> W {
         self.writer
     }
 }
 
-pub struct SeqSerializer<'a, W: io::Write> {
+pub struct SeqSerializer<'a, W: Writer> {
     writer: &'a mut W,
 }
 
-pub struct TupleSerializer<'a, W: io::Write> {
+pub struct TupleSerializer<'a, W: Writer> {
     writer: &'a mut W,
 }
 
-pub struct MapSerializer<'a, W: io::Write> {
+#[cfg(feature = "alloc")]
+pub struct MapSerializer<'a, W: Writer, F> {
     writer: &'a mut W,
+    formatter: &'a mut F,
     entries: Vec<(String, Option<Vec<u8>>)>,
 }
 
-pub struct StructSerializer<'a, W: io::Write> {
+#[cfg(feature = "alloc")]
+pub struct StructSerializer<'a, W: Writer, F> {
     writer: &'a mut W,
+    formatter: &'a mut F,
+    /// `Some(variant)` when this is a tagged struct/tuple variant; the variant name is
+    /// prepended to every field key as `variant.field`. `None` for plain structs and
+    /// for untagged variants.
+    prefix: Option<&'static str>,
     entries: Vec<(String, Option<Vec<u8>>)>,
+    /// Used only when this serializes a tuple variant: the 1-based position of the
+    /// next element, standing in for the field name (`serde::SerializeTupleVariant`
+    /// doesn't carry one).
+    next_index: usize,
 }
 
-impl<'a, W> ser::Serializer for &'a mut Serializer<W>
+impl<'a, W, F> ser::Serializer for &'a mut Serializer<W, F>
 where
-    W: io::Write,
+    W: Writer,
+    F: Formatter,
 {
     type Ok = ();
     type Error = Error;
 
     type SerializeSeq = SeqSerializer<'a, W>;
     type SerializeTuple = TupleSerializer<'a, W>;
-    type SerializeMap = MapSerializer<'a, W>;
-    type SerializeStruct = StructSerializer<'a, W>;
+    #[cfg(feature = "alloc")]
+    type SerializeMap = MapSerializer<'a, W, F>;
+    #[cfg(not(feature = "alloc"))]
+    type SerializeMap = ser::Impossible<(), Error>;
+    #[cfg(feature = "alloc")]
+    type SerializeStruct = StructSerializer<'a, W, F>;
+    #[cfg(not(feature = "alloc"))]
+    type SerializeStruct = ser::Impossible<(), Error>;
     type SerializeTupleStruct = ser::Impossible<(), Error>;
+    #[cfg(feature = "alloc")]
+    type SerializeTupleVariant = StructSerializer<'a, W, F>;
+    #[cfg(not(feature = "alloc"))]
     type SerializeTupleVariant = ser::Impossible<(), Error>;
+    #[cfg(feature = "alloc")]
+    type SerializeStructVariant = StructSerializer<'a, W, F>;
+    #[cfg(not(feature = "alloc"))]
     type SerializeStructVariant = ser::Impossible<(), Error>;
 
     fn serialize_bool(self, _v: bool) -> Result<Self::Ok> {
@@ -249,24 +426,61 @@ where
         })
     }
 
+    #[cfg(feature = "alloc")]
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
         Ok(MapSerializer {
             writer: &mut self.writer,
+            formatter: &mut self.formatter,
             entries: Vec::new(),
         })
     }
 
+    #[cfg(not(feature = "alloc"))]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(Error::RequiresAlloc)
+    }
+
+    #[cfg(feature = "alloc")]
     fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
         Ok(StructSerializer {
             writer: &mut self.writer,
+            formatter: &mut self.formatter,
+            prefix: None,
             entries: Vec::new(),
+            next_index: 0,
         })
     }
 
+    #[cfg(not(feature = "alloc"))]
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(Error::RequiresAlloc)
+    }
+
     fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct> {
         Err(Error::TopLevel)
     }
 
+    #[cfg(feature = "alloc")]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(StructSerializer {
+            writer: &mut self.writer,
+            formatter: &mut self.formatter,
+            prefix: match self.enum_repr {
+                EnumRepr::Tagged => Some(variant),
+                EnumRepr::Untagged => None,
+            },
+            entries: Vec::new(),
+            next_index: 0,
+        })
+    }
+
+    #[cfg(not(feature = "alloc"))]
     fn serialize_tuple_variant(
         self,
         _name: &'static str,
@@ -274,9 +488,30 @@ where
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
-        Err(Error::TopLevel)
+        Err(Error::RequiresAlloc)
     }
 
+    #[cfg(feature = "alloc")]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(StructSerializer {
+            writer: &mut self.writer,
+            formatter: &mut self.formatter,
+            prefix: match self.enum_repr {
+                EnumRepr::Tagged => Some(variant),
+                EnumRepr::Untagged => None,
+            },
+            entries: Vec::new(),
+            next_index: 0,
+        })
+    }
+
+    #[cfg(not(feature = "alloc"))]
     fn serialize_struct_variant(
         self,
         _name: &'static str,
@@ -284,13 +519,13 @@ where
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant> {
-        Err(Error::TopLevel)
+        Err(Error::RequiresAlloc)
     }
 }
 
 impl<W> ser::SerializeSeq for SeqSerializer<'_, W>
 where
-    W: io::Write,
+    W: Writer,
 {
     type Ok = ();
     type Error = Error;
@@ -306,7 +541,7 @@ where
 
 impl<W> ser::SerializeTuple for TupleSerializer<'_, W>
 where
-    W: io::Write,
+    W: Writer,
 {
     type Ok = ();
     type Error = Error;
@@ -320,9 +555,11 @@ where
     }
 }
 
-impl<W> ser::SerializeMap for MapSerializer<'_, W>
+#[cfg(feature = "alloc")]
+impl<W, F> ser::SerializeMap for MapSerializer<'_, W, F>
 where
-    W: io::Write,
+    W: Writer,
+    F: Formatter,
 {
     type Ok = ();
     type Error = Error;
@@ -368,28 +605,34 @@ where
     }
 
     fn end(mut self) -> Result<Self::Ok> {
-        // Sort entries by key alphabetically
-        self.entries.sort_by(|a, b| a.0.cmp(&b.0));
+        self.formatter.sort_entries(&mut self.entries);
 
-        // Write sorted entries
         let mut first = true;
         for (key, value) in self.entries {
             if !mem::replace(&mut first, false) {
-                self.writer.write_all(b"&")?;
+                self.formatter.write_pair_separator(self.writer)?;
             }
-            self.writer.write_all(key.as_bytes())?;
-            if let Some(value) = value {
-                self.writer.write_all(b"=")?;
-                self.writer.write_all(&value)?;
+            self.writer.write_all(self.formatter.encode_key(&key).as_bytes())?;
+            match value {
+                Some(value) => {
+                    self.formatter.write_key_value_separator(self.writer)?;
+                    self.writer.write_all(&self.formatter.encode_value(&value))?;
+                },
+                None if self.formatter.force_key_value_separator() => {
+                    self.formatter.write_key_value_separator(self.writer)?;
+                },
+                None => {},
             }
         }
         Ok(())
     }
 }
 
-impl<W> ser::SerializeStruct for StructSerializer<'_, W>
+#[cfg(feature = "alloc")]
+impl<W, F> ser::SerializeStruct for StructSerializer<'_, W, F>
 where
-    W: io::Write,
+    W: Writer,
+    F: Formatter,
 {
     type Ok = ();
     type Error = Error;
@@ -408,30 +651,95 @@ where
             Err(e) => return Err(e),
         };
 
-        self.entries.push((key.to_string(), serialized_value));
+        let key = match self.prefix {
+            Some(prefix) => format!("{prefix}.{key}"),
+            None => key.to_string(),
+        };
+        self.entries.push((key, serialized_value));
         Ok(())
     }
 
     fn end(mut self) -> Result<Self::Ok> {
-        // Sort entries by key alphabetically
-        self.entries.sort_by(|a, b| a.0.cmp(&b.0));
+        self.formatter.sort_entries(&mut self.entries);
 
-        // Write sorted entries
         let mut first = true;
         for (key, value) in self.entries {
             if !mem::replace(&mut first, false) {
-                self.writer.write_all(b"&")?;
+                self.formatter.write_pair_separator(self.writer)?;
             }
-            self.writer.write_all(key.as_bytes())?;
-            if let Some(value) = value {
-                self.writer.write_all(b"=")?;
-                self.writer.write_all(&value)?;
+            self.writer.write_all(self.formatter.encode_key(&key).as_bytes())?;
+            match value {
+                Some(value) => {
+                    self.formatter.write_key_value_separator(self.writer)?;
+                    self.writer.write_all(&self.formatter.encode_value(&value))?;
+                },
+                None if self.formatter.force_key_value_separator() => {
+                    self.formatter.write_key_value_separator(self.writer)?;
+                },
+                None => {},
             }
         }
         Ok(())
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<W, F> ser::SerializeStructVariant for StructSerializer<'_, W, F>
+where
+    W: Writer,
+    F: Formatter,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + ser::Serialize>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        ser::SerializeStruct::end(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<W, F> ser::SerializeTupleVariant for StructSerializer<'_, W, F>
+where
+    W: Writer,
+    F: Formatter,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<()> {
+        self.next_index += 1;
+        let mut buf = itoa::Buffer::new();
+        let index = buf.format(self.next_index);
+
+        let serialized_value = match flatten::to_vec(value) {
+            Ok(value) => {
+                // Skip field entirely if value is empty (i.e., None or empty)
+                if value.is_empty() {
+                    return Ok(());
+                }
+                Some(value)
+            },
+            Err(Error::Empty) => None,
+            Err(e) => return Err(e),
+        };
+
+        let key = match self.prefix {
+            Some(prefix) => format!("{prefix}.{index}"),
+            None => index.to_string(),
+        };
+        self.entries.push((key, serialized_value));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        ser::SerializeStruct::end(self)
+    }
+}
+
 pub(crate) fn percent_encode(input: &str) -> Cow<'_, str> {
     use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
 
@@ -563,4 +871,103 @@ mod tests {
         let expected2 = "a=1&b=42&d=2";
         assert_eq!(to_string(&u2).unwrap(), expected2);
     }
+
+    #[test]
+    fn test_custom_formatter() {
+        #[derive(Debug, Clone, Copy, Default)]
+        struct CookieFormatter;
+
+        impl Formatter for CookieFormatter {
+            fn write_pair_separator<W: Writer>(&mut self, writer: &mut W) -> Result<()> {
+                writer.write_all(b"; ")?;
+                Ok(())
+            }
+
+            fn sort_entries(&self, _entries: &mut Vec<(String, Option<Vec<u8>>)>) {
+                // Preserve insertion order instead of sorting.
+            }
+        }
+
+        #[derive(Serialize)]
+        struct TestStruct {
+            z: u32,
+            y: u32,
+            x: u32,
+        }
+
+        let u = TestStruct { z: 1, y: 2, x: 3 };
+
+        let mut writer = Vec::new();
+        let mut ser = Serializer::with_formatter(&mut writer, CookieFormatter);
+        u.serialize(&mut ser).unwrap();
+        assert_eq!(String::from_utf8(writer).unwrap(), "z=1; y=2; x=3");
+    }
+
+    #[test]
+    fn test_sig_v4_formatter() {
+        #[derive(Serialize)]
+        struct TestStruct {
+            #[serde(rename = "Action")]
+            action: &'static str,
+            #[serde(rename = "X-Amz-Signature")]
+            signature: &'static str,
+            #[serde(rename = "Marker")]
+            marker: (),
+        }
+
+        let u = TestStruct {
+            action: "ListBuckets",
+            signature: "a b",
+            marker: (),
+        };
+
+        let mut writer = Vec::new();
+        let mut ser = Serializer::with_formatter(&mut writer, SigV4Formatter);
+        u.serialize(&mut ser).unwrap();
+        // Keys are percent-encoded and sorted by their encoded bytes (so the `-` in
+        // `X-Amz-Signature` still sorts after the unencoded `Action`), and the
+        // valueless `Marker` field still gets a trailing `=`.
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "Action=ListBuckets&Marker=&X-Amz-Signature=a%20b"
+        );
+    }
+
+    #[test]
+    fn test_enum_variant_tagged() {
+        #[derive(Serialize)]
+        enum Event {
+            Struct { a: u32, b: u32 },
+            Tuple(u32, u32),
+        }
+
+        let mut writer = Vec::new();
+        let mut ser = Serializer::new(&mut writer);
+        Event::Struct { a: 1, b: 2 }.serialize(&mut ser).unwrap();
+        assert_eq!(String::from_utf8(writer).unwrap(), "Struct.a=1&Struct.b=2");
+
+        let mut writer = Vec::new();
+        let mut ser = Serializer::new(&mut writer);
+        Event::Tuple(1, 2).serialize(&mut ser).unwrap();
+        assert_eq!(String::from_utf8(writer).unwrap(), "Tuple.1=1&Tuple.2=2");
+    }
+
+    #[test]
+    fn test_enum_variant_untagged() {
+        #[derive(Serialize)]
+        enum Event {
+            Struct { a: u32, b: u32 },
+            Tuple(u32, u32),
+        }
+
+        let mut writer = Vec::new();
+        let mut ser = Serializer::new(&mut writer).with_enum_repr(EnumRepr::Untagged);
+        Event::Struct { a: 1, b: 2 }.serialize(&mut ser).unwrap();
+        assert_eq!(String::from_utf8(writer).unwrap(), "a=1&b=2");
+
+        let mut writer = Vec::new();
+        let mut ser = Serializer::new(&mut writer).with_enum_repr(EnumRepr::Untagged);
+        Event::Tuple(1, 2).serialize(&mut ser).unwrap();
+        assert_eq!(String::from_utf8(writer).unwrap(), "1=1&2=2");
+    }
 }
@@ -1,9 +1,9 @@
 use std::borrow::Cow;
-use std::{io, mem};
+use std::mem;
 
 use serde::ser;
 
-use super::{Error, flatten, key};
+use super::{Error, Writer, flatten, key};
 
 enum PairState<'a> {
     WaitingForKey,
@@ -18,7 +18,7 @@ pub struct PairSerializer<'a, W> {
 
 impl<'a, W> PairSerializer<'a, W>
 where
-    W: io::Write,
+    W: Writer,
 {
     pub(crate) fn new(writer: &'a mut W) -> Self {
         Self {
@@ -30,7 +30,7 @@ where
 
 impl<W> ser::Serializer for PairSerializer<'_, W>
 where
-    W: io::Write,
+    W: Writer,
 {
     type Ok = ();
     type Error = Error;
@@ -192,7 +192,7 @@ where
 
 impl<W> ser::SerializeTuple for PairSerializer<'_, W>
 where
-    W: io::Write,
+    W: Writer,
 {
     type Ok = ();
     type Error = Error;
@@ -209,16 +209,21 @@ where
                 Ok(())
             },
             PairState::WaitingForValue { key } => {
-                let result = {
-                    let mut value_serializer = flatten::FlattenSerializer::new(self.writer);
-                    value.serialize(&mut value_serializer)
-                };
-                // recover the state if the value serialization fails
-                if result.is_err() {
-                    self.state = PairState::WaitingForValue { key };
-                    return result;
+                // `FlattenSerializer` still writes through a plain `std::io::Write`
+                // buffer, so render the value into one before copying it through
+                // `self.writer` (the generic `Writer` sink).
+                let result = flatten::to_vec(value);
+                match result {
+                    Ok(bytes) => {
+                        self.writer.write_all(&bytes)?;
+                        Ok(())
+                    },
+                    Err(err) => {
+                        // recover the state if the value serialization fails
+                        self.state = PairState::WaitingForValue { key };
+                        Err(err)
+                    },
                 }
-                Ok(())
             },
             PairState::Done => Err(Error::done()),
         }
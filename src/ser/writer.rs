@@ -0,0 +1,59 @@
+use super::{Error, Result};
+
+/// A sink for serialized bytes, used in place of [`std::io::Write`] so the serializer can
+/// run in `no_std` environments (embedded targets, kernel code) as well as on top of `std`.
+///
+/// Modeled on cbor-smol's `Writer` trait: a single all-or-nothing write.
+pub trait Writer {
+    /// Write the whole of `buf`. Implementations must not write a partial prefix on error.
+    fn write_all(&mut self, buf: &[u8]) -> Result<()>;
+}
+
+#[cfg(feature = "std")]
+impl<W> Writer for W
+where
+    W: std::io::Write,
+{
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        std::io::Write::write_all(self, buf)?;
+        Ok(())
+    }
+}
+
+/// A [`Writer`] that writes into a caller-supplied, fixed-size `&mut [u8]` instead of
+/// allocating. Used by [`super::to_slice`] on targets without `alloc`.
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Wraps `buf`; writes start at offset `0`.
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, position: 0 }
+    }
+
+    /// Number of bytes written so far.
+    #[inline]
+    pub fn bytes_written(&self) -> usize {
+        self.position
+    }
+
+    /// Consumes the writer, returning the written prefix of the backing buffer.
+    #[inline]
+    pub fn into_inner(self) -> &'a mut [u8] {
+        &mut self.buf[..self.position]
+    }
+}
+
+impl Writer for SliceWriter<'_> {
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        let end = self.position.checked_add(buf.len()).ok_or(Error::BufferFull)?;
+        let dest = self.buf.get_mut(self.position..end).ok_or(Error::BufferFull)?;
+        dest.copy_from_slice(buf);
+        self.position = end;
+        Ok(())
+    }
+}
@@ -1,5 +1,13 @@
+use chrono::{DateTime, Utc};
+
 use crate::ser::percent_encode;
 
+/// Format a timestamp as an RFC 7231 HTTP-date (e.g. `Fri, 13 Nov 2015 00:00:00 GMT`), the
+/// form OSS requires for conditional-request headers like `If-Modified-Since`.
+pub(crate) fn format_http_date(datetime: DateTime<Utc>) -> String {
+    datetime.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
 #[inline]
 pub fn escape_path(url_path: &str) -> String {
     url_path
@@ -8,3 +16,165 @@ pub fn escape_path(url_path: &str) -> String {
         .collect::<Vec<_>>()
         .join("/")
 }
+
+/// Reflected CRC-64/ECMA-182 table, used for the `x-oss-hash-crc64ecma` checksum OSS
+/// returns on uploads. Polynomial `0x42F0E1EBA9EA3693`, reflected form
+/// `0xC96C5795D7870F42`.
+const CRC64_ECMA_POLY: u64 = 0xC96C_5795_D787_0F42;
+
+const fn build_crc64_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ CRC64_ECMA_POLY } else { crc >> 1 };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const CRC64_ECMA_TABLE: [u64; 256] = build_crc64_table();
+
+/// Incremental CRC-64/ECMA-182 (reflected) hasher matching the checksum OSS reports as
+/// `x-oss-hash-crc64ecma`.
+#[derive(Debug, Clone)]
+pub(crate) struct Crc64 {
+    register: u64,
+}
+
+impl Crc64 {
+    pub(crate) fn new() -> Self {
+        Self { register: u64::MAX }
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            let index = ((self.register ^ u64::from(byte)) & 0xFF) as usize;
+            self.register = (self.register >> 8) ^ CRC64_ECMA_TABLE[index];
+        }
+    }
+
+    /// The checksum computed so far, formatted the way OSS reports it: an unsigned
+    /// 64-bit decimal string.
+    pub(crate) fn digest(&self) -> String {
+        (self.register ^ u64::MAX).to_string()
+    }
+}
+
+impl Default for Crc64 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn gf2_matrix_times(mat: &[u64; 64], mut vec: u64) -> u64 {
+    let mut sum = 0u64;
+    let mut row = 0;
+    while vec != 0 {
+        if vec & 1 != 0 {
+            sum ^= mat[row];
+        }
+        vec >>= 1;
+        row += 1;
+    }
+    sum
+}
+
+fn gf2_matrix_square(square: &mut [u64; 64], mat: &[u64; 64]) {
+    for (n, slot) in square.iter_mut().enumerate() {
+        *slot = gf2_matrix_times(mat, mat[n]);
+    }
+}
+
+/// Combine two independently-computed CRC-64/ECMA-182 checksums as though the bytes they
+/// were computed over had been hashed back-to-back, without rehashing either one.
+///
+/// `crc1` and `crc2` are the finished checksums (as reported by OSS) of the first and
+/// second byte ranges respectively, and `len2` is the length in bytes of the second range.
+/// This is what lets multipart/parallel downloads and uploads fold per-part CRCs into a
+/// whole-object CRC: build the "shift the running register by `len2` zero bytes" operator
+/// as a 64x64 matrix over GF(2), raise it to the needed power via repeated squaring
+/// (exponentiation by `len2`'s bits), apply it to `crc1`, then XOR in `crc2`.
+pub(crate) fn crc64_combine(crc1: u64, crc2: u64, mut len2: u64) -> u64 {
+    if len2 == 0 {
+        return crc1;
+    }
+
+    // `odd` starts as the operator for "shift by one zero bit": its first column is the
+    // (reflected) polynomial itself, and the rest is the identity shifted down by one.
+    let mut odd = [0u64; 64];
+    odd[0] = CRC64_ECMA_POLY;
+    let mut row = 1u64;
+    for slot in odd.iter_mut().skip(1) {
+        *slot = row;
+        row <<= 1;
+    }
+
+    let mut even = [0u64; 64];
+    gf2_matrix_square(&mut even, &odd); // shift by 2 zero bits
+    gf2_matrix_square(&mut odd, &even); // shift by 4 zero bits (= 1 zero byte)
+
+    let mut crc1 = crc1;
+    loop {
+        gf2_matrix_square(&mut even, &odd);
+        if len2 & 1 != 0 {
+            crc1 = gf2_matrix_times(&even, crc1);
+        }
+        len2 >>= 1;
+        if len2 == 0 {
+            break;
+        }
+
+        gf2_matrix_square(&mut odd, &even);
+        if len2 & 1 != 0 {
+            crc1 = gf2_matrix_times(&odd, crc1);
+        }
+        len2 >>= 1;
+        if len2 == 0 {
+            break;
+        }
+    }
+
+    crc1 ^ crc2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_matches_hashing_the_concatenation() {
+        let part1 = b"the quick brown fox ";
+        let part2 = b"jumps over the lazy dog";
+
+        let mut whole = Crc64::new();
+        whole.update(part1);
+        whole.update(part2);
+        let expected: u64 = whole.digest().parse().unwrap();
+
+        let mut crc1 = Crc64::new();
+        crc1.update(part1);
+        let crc1: u64 = crc1.digest().parse().unwrap();
+
+        let mut crc2 = Crc64::new();
+        crc2.update(part2);
+        let crc2: u64 = crc2.digest().parse().unwrap();
+
+        let combined = crc64_combine(crc1, crc2, part2.len() as u64);
+        assert_eq!(combined, expected);
+    }
+
+    #[test]
+    fn combine_with_empty_second_part_is_identity() {
+        let mut crc1 = Crc64::new();
+        crc1.update(b"some bytes");
+        let crc1: u64 = crc1.digest().parse().unwrap();
+
+        assert_eq!(crc64_combine(crc1, 0, 0), crc1);
+    }
+}